@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Entries kept in memory, older ones are only in the log file
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    /// Level of a status message from its icon prefix
+    pub fn of_status(message: &str) -> Self {
+        if message.starts_with('✓') {
+            LogLevel::Success
+        } else if message.starts_with('⚠') {
+            LogLevel::Warning
+        } else if message.starts_with('✗') {
+            LogLevel::Error
+        } else {
+            LogLevel::Info
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Success => "OK",
+            LogLevel::Warning => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: SystemTime,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Time of day in UTC, like `14:03:59`
+    pub fn clock(&self) -> String {
+        let secs = unix_secs(self.time);
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60
+        )
+    }
+
+    /// Full UTC timestamp, like `2024-05-01T14:03:59Z`
+    pub fn timestamp(&self) -> String {
+        let secs = unix_secs(self.time);
+        let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+        format!("{:04}-{:02}-{:02}T{}Z", year, month, day, self.clock())
+    }
+}
+
+/// Timestamped record of what the app did, mirrored to a log file
+pub struct ActivityLog {
+    entries: VecDeque<LogEntry>,
+    /// File every entry is appended to, dropped after the first write failure
+    file: Option<PathBuf>,
+}
+
+impl ActivityLog {
+    pub fn new(file: Option<PathBuf>) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            file,
+        }
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn file(&self) -> Option<&PathBuf> {
+        self.file.as_ref()
+    }
+
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        let entry = LogEntry {
+            time: SystemTime::now(),
+            level,
+            message: message.into(),
+        };
+
+        let failed = self.file.as_ref().and_then(|path| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| {
+                    writeln!(
+                        file,
+                        "{} [{}] {}",
+                        entry.timestamp(),
+                        entry.level.label(),
+                        entry.message
+                    )
+                })
+                .err()
+                .map(|e| format!("⚠ Could not write {}: {}", path.display(), e))
+        });
+
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+
+        // Reported after the entry that failed to write, which stays in memory
+        if let Some(message) = failed {
+            self.file = None;
+            self.push(LogLevel::Warning, message);
+        }
+    }
+
+    /// Record a status message, taking the level from its icon
+    pub fn status(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        self.push(LogLevel::of_status(&message), message);
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Year, month and day of a count of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days_from_civil inverse, eras of 400 years
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_failures_are_logged_after_the_entry() {
+        let path = std::env::temp_dir()
+            .join(format!("tp_sync_missing_{}", std::process::id()))
+            .join("tp_sync.log");
+        let mut log = ActivityLog::new(Some(path));
+        log.status("✓ Installed 'Raids'");
+        log.status("○ Nothing to do");
+
+        let messages: Vec<_> = log.entries().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0], "✓ Installed 'Raids'");
+        assert!(messages[1].starts_with("⚠ Could not write"));
+        assert_eq!(messages[2], "○ Nothing to do");
+        assert!(log.file().is_none());
+    }
+
+    #[test]
+    fn keeps_the_newest_entries() {
+        let mut log = ActivityLog::new(None);
+        for i in 0..MAX_ENTRIES + 5 {
+            log.push(LogLevel::Info, i.to_string());
+        }
+        assert_eq!(log.entries().count(), MAX_ENTRIES);
+        assert_eq!(log.entries().next().unwrap().message, "5");
+    }
+
+    #[test]
+    fn timestamps_are_utc() {
+        let entry = LogEntry {
+            time: UNIX_EPOCH + std::time::Duration::from_secs(1_714_572_239),
+            level: LogLevel::Info,
+            message: String::new(),
+        };
+        assert_eq!(entry.timestamp(), "2024-05-01T14:03:59Z");
+    }
+}
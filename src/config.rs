@@ -1,4 +1,7 @@
+use crate::keymap::Keymap;
+use crate::theme::{ColorRole, ColorValue, Theme, ThemeName};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +15,38 @@ pub struct AppConfig {
 
     #[serde(default = "default_repo_name")]
     pub remote_repo_name: String,
+
+    /// Local TacO / Blish HUD marker pack to offer next to the remote repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker_pack_path: Option<PathBuf>,
+
+    /// Only offer this category of the marker pack, e.g. `tyria.waypoints`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker_pack_category: Option<String>,
+
+    /// Maximum distance between teleports on the same map to count as duplicates
+    #[serde(default = "default_duplicate_distance")]
+    pub duplicate_distance: f32,
+
+    /// Folder exported group files are written to, defaults to `exports` next to the executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_dir: Option<PathBuf>,
+
+    /// File the activity log is appended to, defaults to `tp_sync.log` next to the executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+
+    /// Built-in colour scheme
+    #[serde(default, skip_serializing_if = "ThemeName::is_default")]
+    pub theme: ThemeName,
+
+    /// Colours overriding parts of the theme
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<ColorRole, ColorValue>,
+
+    /// Keys bound to actions, only the actions that differ from the defaults
+    #[serde(default, skip_serializing_if = "Keymap::is_default")]
+    pub keymap: Keymap,
 }
 
 fn default_repo_owner() -> String {
@@ -22,6 +57,10 @@ fn default_repo_name() -> String {
     "gw2-oktw-teleports".to_string()
 }
 
+fn default_duplicate_distance() -> f32 {
+    1.0
+}
+
 impl AppConfig {
     /// Load config from disk, returns None if not found
     pub fn load() -> crate::Result<Option<Self>> {
@@ -33,15 +72,24 @@ impl AppConfig {
 
         let content = std::fs::read_to_string(&path)?;
         let config: Self = toml::from_str(&content).map_err(|e| {
-            crate::TeleportError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse config: {}", e),
-            ))
+            crate::TeleportError::Config(format!("Failed to parse {}: {}", path.display(), e))
         })?;
+        config.check_settings()?;
 
         Ok(Some(config))
     }
 
+    /// Reject settings that parse but can't be used
+    fn check_settings(&self) -> crate::Result<()> {
+        if !self.duplicate_distance.is_finite() || self.duplicate_distance < 0.0 {
+            return Err(crate::TeleportError::Config(format!(
+                "duplicate_distance must be a number of at least 0, not {}",
+                self.duplicate_distance
+            )));
+        }
+        self.keymap.validate()
+    }
+
     /// Save config to disk
     pub fn save(&self) -> crate::Result<()> {
         let path = Self::config_path()?;
@@ -52,10 +100,7 @@ impl AppConfig {
         }
 
         let content = toml::to_string_pretty(self).map_err(|e| {
-            crate::TeleportError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to serialize config: {}", e),
-            ))
+            crate::TeleportError::Config(format!("Failed to serialize config: {}", e))
         })?;
         std::fs::write(&path, content)?;
 
@@ -63,7 +108,33 @@ impl AppConfig {
     }
 
     /// Get config file path (in the same directory as the executable)
-    fn config_path() -> crate::Result<PathBuf> {
+    pub fn config_path() -> crate::Result<PathBuf> {
+        Ok(Self::exe_dir()?.join("config.toml"))
+    }
+
+    /// Get the folder exported group files are written to
+    pub fn export_dir(&self) -> crate::Result<PathBuf> {
+        match &self.export_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(Self::exe_dir()?.join("exports")),
+        }
+    }
+
+    /// Get the file the activity log is appended to
+    pub fn log_file(&self) -> crate::Result<PathBuf> {
+        match &self.log_file {
+            Some(path) => Ok(path.clone()),
+            None => Ok(Self::exe_dir()?.join("tp_sync.log")),
+        }
+    }
+
+    /// Get the colours to draw the interface with
+    pub fn theme(&self) -> Theme {
+        Theme::load(self.theme, &self.colors)
+    }
+
+    /// Get the directory containing the executable
+    fn exe_dir() -> crate::Result<PathBuf> {
         let exe_path = std::env::current_exe()?;
         let exe_dir = exe_path.parent().ok_or_else(|| {
             crate::TeleportError::IoError(std::io::Error::new(
@@ -72,7 +143,7 @@ impl AppConfig {
             ))
         })?;
 
-        Ok(exe_dir.join("config.toml"))
+        Ok(exe_dir.to_path_buf())
     }
 
     /// Create a new config with the given teleport.json path
@@ -81,18 +152,23 @@ impl AppConfig {
             teleport_json_path,
             remote_repo_owner: default_repo_owner(),
             remote_repo_name: default_repo_name(),
+            marker_pack_path: None,
+            marker_pack_category: None,
+            duplicate_distance: default_duplicate_distance(),
+            export_dir: None,
+            log_file: None,
+            theme: ThemeName::default(),
+            colors: BTreeMap::new(),
+            keymap: Keymap::default(),
         }
     }
 
     /// Validate that the teleport.json path exists and is readable
     pub fn validate(&self) -> crate::Result<()> {
         if !self.teleport_json_path.exists() {
-            return Err(crate::TeleportError::IoError(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                format!(
-                    "teleport.json not found at: {}",
-                    self.teleport_json_path.display()
-                ),
+            return Err(crate::TeleportError::Config(format!(
+                "teleport.json not found at: {}",
+                self.teleport_json_path.display()
             )));
         }
 
@@ -102,3 +178,28 @@ impl AppConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(duplicate_distance: &str) -> AppConfig {
+        toml::from_str(&format!(
+            "teleport_json_path = \"teleport.json\"\nduplicate_distance = {}",
+            duplicate_distance
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn duplicate_distance_must_be_finite_and_not_negative() {
+        assert!(config("0.0").check_settings().is_ok());
+        assert!(config("2.5").check_settings().is_ok());
+        for invalid in ["-1.0", "nan", "inf"] {
+            assert!(matches!(
+                config(invalid).check_settings(),
+                Err(crate::TeleportError::Config(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::types::{Teleport, TeleportGroup};
+
+/// Difference of one teleport between two versions of a group
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeleportDiff {
+    /// Only in the new version
+    Added(Teleport),
+    /// Only in the old version
+    Removed(Teleport),
+    /// In both versions with different fields
+    Modified { old: Teleport, new: Teleport },
+    /// Identical in both versions
+    Unchanged(Teleport),
+}
+
+impl TeleportDiff {
+    pub fn is_change(&self) -> bool {
+        !matches!(self, TeleportDiff::Unchanged(_))
+    }
+
+    /// Coordinate change of a modified teleport, new minus old
+    pub fn coordinate_delta(&self) -> Option<[f32; 3]> {
+        match self {
+            TeleportDiff::Modified { old, new } if old.coordinates != new.coordinates => Some([
+                new.coordinates[0] - old.coordinates[0],
+                new.coordinates[1] - old.coordinates[1],
+                new.coordinates[2] - old.coordinates[2],
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Whether a modified teleport's tooltip changed
+    pub fn tooltip_changed(&self) -> bool {
+        matches!(self, TeleportDiff::Modified { old, new } if old.tooltip != new.tooltip)
+    }
+}
+
+/// Per-teleport differences between an old and a new version of a group
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupDiff {
+    pub entries: Vec<TeleportDiff>,
+}
+
+impl GroupDiff {
+    /// Compare two versions of a group, pairing teleports by name
+    ///
+    /// Teleports sharing a name are paired in order. Entries follow the new
+    /// version's order, with removed teleports placed after their old predecessor.
+    pub fn between(old: &TeleportGroup, new: &TeleportGroup) -> Self {
+        let mut paired = vec![false; old.teleports.len()];
+        let mut matches: Vec<Option<usize>> = Vec::with_capacity(new.teleports.len());
+        for teleport in &new.teleports {
+            let found = old
+                .teleports
+                .iter()
+                .enumerate()
+                .position(|(i, t)| !paired[i] && t.name == teleport.name);
+            if let Some(i) = found {
+                paired[i] = true;
+            }
+            matches.push(found);
+        }
+
+        let mut entries = Vec::with_capacity(old.teleports.len().max(new.teleports.len()));
+        let mut next_old = 0;
+        for (teleport, found) in new.teleports.iter().zip(matches) {
+            if let Some(i) = found {
+                // Removed teleports that came before this one in the old version
+                while next_old < i {
+                    if !paired[next_old] {
+                        entries.push(TeleportDiff::Removed(old.teleports[next_old].clone()));
+                    }
+                    next_old += 1;
+                }
+                next_old = next_old.max(i + 1);
+
+                let before = &old.teleports[i];
+                entries.push(if before == teleport {
+                    TeleportDiff::Unchanged(teleport.clone())
+                } else {
+                    TeleportDiff::Modified {
+                        old: before.clone(),
+                        new: teleport.clone(),
+                    }
+                });
+            } else {
+                entries.push(TeleportDiff::Added(teleport.clone()));
+            }
+        }
+        for (i, teleport) in old.teleports.iter().enumerate().skip(next_old) {
+            if !paired[i] {
+                entries.push(TeleportDiff::Removed(teleport.clone()));
+            }
+        }
+
+        Self { entries }
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.entries.iter().any(TeleportDiff::is_change)
+    }
+
+    /// Number of added, removed and modified teleports
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let mut counts = (0, 0, 0);
+        for entry in &self.entries {
+            match entry {
+                TeleportDiff::Added(_) => counts.0 += 1,
+                TeleportDiff::Removed(_) => counts.1 += 1,
+                TeleportDiff::Modified { .. } => counts.2 += 1,
+                TeleportDiff::Unchanged(_) => {}
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(teleports: &[(&str, f32)]) -> TeleportGroup {
+        let mut group = TeleportGroup::new("Raids");
+        for (name, x) in teleports {
+            group
+                .teleports
+                .push(Teleport::new(*name, 1062, [*x, 0.0, 0.0]));
+        }
+        group
+    }
+
+    #[test]
+    fn identical_groups_have_no_changes() {
+        let old = group(&[("Gate", 1.0), ("Boss", 2.0)]);
+        let diff = GroupDiff::between(&old, &old.clone());
+        assert!(!diff.has_changes());
+        assert_eq!(diff.counts(), (0, 0, 0));
+        assert_eq!(diff.entries.len(), 2);
+    }
+
+    #[test]
+    fn changes_follow_the_new_order() {
+        let old = group(&[("A", 1.0), ("B", 2.0), ("C", 3.0), ("D", 4.0)]);
+        let mut new = group(&[("A", 1.5), ("C", 3.0), ("E", 5.0), ("D", 4.0)]);
+        new.teleports[3].tooltip = Some("Boss".to_string());
+
+        let diff = GroupDiff::between(&old, &new);
+        let kinds: Vec<_> = diff
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                TeleportDiff::Added(t) => format!("+{}", t.name),
+                TeleportDiff::Removed(t) => format!("-{}", t.name),
+                TeleportDiff::Modified { new, .. } => format!("~{}", new.name),
+                TeleportDiff::Unchanged(t) => t.name.clone(),
+            })
+            .collect();
+        assert_eq!(kinds, ["~A", "-B", "C", "+E", "~D"]);
+        assert_eq!(diff.counts(), (1, 1, 2));
+
+        assert_eq!(diff.entries[0].coordinate_delta(), Some([0.5, 0.0, 0.0]));
+        assert!(!diff.entries[0].tooltip_changed());
+        assert_eq!(diff.entries[4].coordinate_delta(), None);
+        assert!(diff.entries[4].tooltip_changed());
+    }
+
+    #[test]
+    fn same_name_teleports_pair_in_order() {
+        let old = group(&[("Waypoint", 1.0), ("Waypoint", 2.0)]);
+        let new = group(&[("Waypoint", 2.0)]);
+
+        let diff = GroupDiff::between(&old, &new);
+        assert_eq!(diff.counts(), (0, 1, 1));
+        assert_eq!(diff.entries[0].coordinate_delta(), Some([1.0, 0.0, 0.0]));
+        assert!(matches!(&diff.entries[1], TeleportDiff::Removed(t) if t.coordinates[0] == 2.0));
+    }
+}
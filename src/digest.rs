@@ -0,0 +1,199 @@
+use crate::types::TeleportGroup;
+use crate::{Result, TeleportError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+
+const PREFIX: &str = "sha256:";
+
+/// SHA-256 digest of a group's canonical JSON
+///
+/// Canonical JSON has object keys sorted and no whitespace, so the digest only
+/// changes when the content does and can be stored or compared between machines.
+/// It is written as `sha256:<64 hex digits>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupDigest([u8; 32]);
+
+impl GroupDigest {
+    /// Digest of a group, including its name and any extra fields
+    pub fn of(group: &TeleportGroup) -> Self {
+        Self::of_value(&to_value(group))
+    }
+
+    /// Digest of a group's teleports and of the group fields `like` also has
+    ///
+    /// The name is left out, so a local group can be compared with a remote version
+    /// of it regardless of the name it was installed under or local-only fields.
+    pub fn of_content(group: &TeleportGroup, like: &TeleportGroup) -> Self {
+        let mut value = to_value(group);
+        if let Value::Object(map) = &mut value {
+            map.retain(|key, _| key == "teleports" || like.other_fields.contains_key(key));
+        }
+        Self::of_value(&value)
+    }
+
+    fn of_value(value: &Value) -> Self {
+        let mut json = String::new();
+        write_canonical(value, &mut json);
+        Self(Sha256::digest(json.as_bytes()).into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// First 12 hex digits, enough to tell digests apart at a glance
+    pub fn short(&self) -> String {
+        self.hex()[..12].to_string()
+    }
+
+    fn hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+impl fmt::Display for GroupDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", PREFIX, self.hex())
+    }
+}
+
+impl FromStr for GroupDigest {
+    type Err = TeleportError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            TeleportError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid group digest '{}'", s),
+            ))
+        };
+        let hex = s.strip_prefix(PREFIX).unwrap_or(s);
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(invalid());
+        }
+
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl Serialize for GroupDigest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for GroupDigest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+fn to_value(group: &TeleportGroup) -> Value {
+    // Names are strings and non-finite floats become null, so this can't fail
+    serde_json::to_value(group).expect("teleport groups serialize to JSON")
+}
+
+/// Serialize a JSON value with sorted object keys and no whitespace
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(json: &str) -> TeleportGroup {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn key_order_does_not_change_the_digest() {
+        let a = group(
+            r#"{"name": "Raids", "version": 2, "teleports": [
+                {"name": "Gate", "map": 1062, "coordinates": [1.5, -2, 3], "color": "red"}
+            ]}"#,
+        );
+        let b = group(
+            r#"{"teleports": [
+                {"color": "red", "coordinates": [1.5, -2, 3], "map": 1062, "name": "Gate"}
+            ], "version": 2, "name": "Raids"}"#,
+        );
+        assert_eq!(a.digest(), b.digest());
+
+        let mut moved = a.clone();
+        moved.teleports[0].coordinates[2] = 4.0;
+        assert_ne!(a.digest(), moved.digest());
+    }
+
+    #[test]
+    fn content_digest_ignores_the_name_and_local_only_fields() {
+        let remote = group(
+            r#"{"name": "Raids", "author": "remote", "teleports": [
+                {"name": "Gate", "map": 1062, "coordinates": [1.5, -2, 3]}
+            ]}"#,
+        );
+        let local = group(
+            r#"{"name": "Raids (remote)", "author": "remote", "color": "red", "teleports": [
+                {"name": "Gate", "map": 1062, "coordinates": [1.5, -2, 3]}
+            ]}"#,
+        );
+        let content = GroupDigest::of_content(&remote, &remote);
+        assert_eq!(GroupDigest::of_content(&local, &remote), content);
+        assert_ne!(local.digest(), remote.digest());
+
+        let mut edited = remote.clone();
+        edited.other_fields.insert("author".into(), "local".into());
+        assert_ne!(GroupDigest::of_content(&edited, &remote), content);
+    }
+
+    #[test]
+    fn digest_is_stable() {
+        let digest = group(
+            r#"{"name": "Raids", "version": 2, "teleports": [
+                {"name": "Gate", "map": 1062, "coordinates": [1.5, -2, 3], "color": "red"}
+            ]}"#,
+        )
+        .digest();
+        assert_eq!(
+            digest.to_string(),
+            "sha256:23d570fd5dd9ae087bbce526bc06ad28b361458146acb0a5be72a6f03e476c54"
+        );
+        assert_eq!(digest.short(), "23d570fd5dd9");
+        assert_eq!(digest.to_string().parse::<GroupDigest>().unwrap(), digest);
+        assert!("sha256:1234".parse::<GroupDigest>().is_err());
+    }
+}
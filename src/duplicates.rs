@@ -0,0 +1,354 @@
+use crate::types::{Teleport, TeleportGroup};
+
+/// Location of a teleport inside a list of groups
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeleportLocation {
+    pub group: String,
+    /// Position of the teleport inside its group
+    pub index: usize,
+    pub name: String,
+}
+
+/// Teleports on the same map that lie within the configured distance of each other
+///
+/// The first member is the copy that is kept when deduplicating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCluster {
+    pub map: u32,
+    pub members: Vec<TeleportLocation>,
+}
+
+impl DuplicateCluster {
+    /// The copy that is kept when deduplicating
+    pub fn keeper(&self) -> &TeleportLocation {
+        &self.members[0]
+    }
+
+    /// The copies that are removed or merged when deduplicating
+    pub fn duplicates(&self) -> &[TeleportLocation] {
+        &self.members[1..]
+    }
+}
+
+/// How duplicate copies are handled when deduplicating
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DedupeMode {
+    /// Drop the duplicate copies
+    Remove,
+    /// Fold tooltips and extra fields of the duplicates into the kept copy, then drop them
+    #[default]
+    Merge,
+}
+
+impl DedupeMode {
+    /// All modes in the order they are offered to the user
+    pub const ALL: [DedupeMode; 2] = [DedupeMode::Merge, DedupeMode::Remove];
+
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            DedupeMode::Remove => "Remove",
+            DedupeMode::Merge => "Merge",
+        }
+    }
+}
+
+/// Finds teleports on the same map within a distance of each other
+#[derive(Debug, Clone)]
+pub struct DuplicateFinder {
+    max_distance: f32,
+    across_groups: bool,
+}
+
+impl DuplicateFinder {
+    /// Create a finder matching teleports within `max_distance` of each other, across all groups
+    pub fn new(max_distance: f32) -> Self {
+        Self {
+            max_distance,
+            across_groups: true,
+        }
+    }
+
+    /// Only match teleports that belong to the same group
+    pub fn within_groups_only(mut self) -> Self {
+        self.across_groups = false;
+        self
+    }
+
+    /// Check whether two teleports count as duplicates
+    pub fn is_duplicate(&self, a: &Teleport, b: &Teleport) -> bool {
+        a.map == b.map && distance(a, b).is_some_and(|d| d <= self.max_distance)
+    }
+
+    /// Find duplicate clusters in the given groups, in group and teleport order
+    ///
+    /// Clusters are not transitive. Each one is built around its first teleport, the
+    /// keeper, and only takes teleports within the distance of the keeper. In a chain
+    /// A–B–C where A and C are too far apart, A and B form a cluster and C is left on
+    /// its own, so which copies are grouped depends on group and teleport order.
+    pub fn find(&self, groups: &[TeleportGroup]) -> Vec<DuplicateCluster> {
+        let all: Vec<(usize, usize, &Teleport)> = groups
+            .iter()
+            .enumerate()
+            .flat_map(|(g, group)| {
+                group
+                    .teleports
+                    .iter()
+                    .enumerate()
+                    .map(move |(t, teleport)| (g, t, teleport))
+            })
+            .collect();
+
+        let mut assigned = vec![false; all.len()];
+        let mut clusters = Vec::new();
+
+        for i in 0..all.len() {
+            if assigned[i] {
+                continue;
+            }
+            let (keeper_group, _, keeper) = all[i];
+            let mut members = vec![i];
+
+            for j in (i + 1)..all.len() {
+                let (group, _, teleport) = all[j];
+                if assigned[j] || (!self.across_groups && group != keeper_group) {
+                    continue;
+                }
+                if self.is_duplicate(keeper, teleport) {
+                    members.push(j);
+                }
+            }
+
+            if members.len() > 1 {
+                for &m in &members {
+                    assigned[m] = true;
+                }
+                clusters.push(DuplicateCluster {
+                    map: keeper.map,
+                    members: members
+                        .into_iter()
+                        .map(|m| {
+                            let (g, t, teleport) = all[m];
+                            TeleportLocation {
+                                group: groups[g].name.clone(),
+                                index: t,
+                                name: teleport.name.clone(),
+                            }
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        clusters
+    }
+}
+
+/// Summary of a deduplication run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DedupeReport {
+    pub clusters: usize,
+    pub removed: usize,
+}
+
+/// Remove or merge the duplicates of each cluster in place
+pub fn dedupe_groups(
+    groups: &mut [TeleportGroup],
+    clusters: &[DuplicateCluster],
+    mode: DedupeMode,
+) -> DedupeReport {
+    let mut removals: Vec<(String, usize)> = Vec::new();
+
+    for cluster in clusters {
+        if mode == DedupeMode::Merge {
+            let copies: Vec<Teleport> = cluster
+                .duplicates()
+                .iter()
+                .filter_map(|loc| find_teleport(groups, loc).cloned())
+                .collect();
+            if let Some(keeper) = find_teleport_mut(groups, cluster.keeper()) {
+                for copy in copies {
+                    merge_teleport(keeper, copy);
+                }
+            }
+        }
+        removals.extend(
+            cluster
+                .duplicates()
+                .iter()
+                .map(|loc| (loc.group.clone(), loc.index)),
+        );
+    }
+
+    // Remove from the back so earlier indices stay valid
+    removals.sort_by_key(|r| std::cmp::Reverse(r.1));
+    let mut removed = 0;
+    for (group_name, index) in removals {
+        if let Some(group) = groups.iter_mut().find(|g| g.name == group_name)
+            && index < group.teleports.len()
+        {
+            group.teleports.remove(index);
+            removed += 1;
+        }
+    }
+
+    DedupeReport {
+        clusters: clusters.len(),
+        removed,
+    }
+}
+
+fn find_teleport<'a>(groups: &'a [TeleportGroup], loc: &TeleportLocation) -> Option<&'a Teleport> {
+    groups
+        .iter()
+        .find(|g| g.name == loc.group)
+        .and_then(|g| g.teleports.get(loc.index))
+}
+
+fn find_teleport_mut<'a>(
+    groups: &'a mut [TeleportGroup],
+    loc: &TeleportLocation,
+) -> Option<&'a mut Teleport> {
+    groups
+        .iter_mut()
+        .find(|g| g.name == loc.group)
+        .and_then(|g| g.teleports.get_mut(loc.index))
+}
+
+/// Fold a duplicate into the kept copy, keeping the kept copy's values on conflict
+fn merge_teleport(keeper: &mut Teleport, copy: Teleport) {
+    keeper.tooltip = match (keeper.tooltip.take(), copy.tooltip) {
+        (Some(a), Some(b)) if a != b && !a.contains(&b) => Some(format!("{}\n{}", a, b)),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    };
+    for (key, value) in copy.other_fields {
+        keeper.other_fields.entry(key).or_insert(value);
+    }
+}
+
+/// Euclidean distance between two teleports, or None if either has non-finite coordinates
+fn distance(a: &Teleport, b: &Teleport) -> Option<f32> {
+    let d = a
+        .coordinates
+        .iter()
+        .zip(&b.coordinates)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt();
+    d.is_finite().then_some(d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, teleports: &[(&str, u32, [f32; 3])]) -> TeleportGroup {
+        let mut group = TeleportGroup::new(name);
+        group.teleports = teleports
+            .iter()
+            .map(|(name, map, coordinates)| Teleport::new(*name, *map, *coordinates))
+            .collect();
+        group
+    }
+
+    fn names(cluster: &DuplicateCluster) -> Vec<&str> {
+        cluster.members.iter().map(|m| m.name.as_str()).collect()
+    }
+
+    #[test]
+    fn matches_same_map_within_distance_only() {
+        let groups = [group(
+            "A",
+            &[
+                ("one", 1, [0.0, 0.0, 0.0]),
+                ("near", 1, [3.0, 4.0, 0.0]),
+                ("far", 1, [10.0, 0.0, 0.0]),
+                ("other map", 2, [0.0, 0.0, 0.0]),
+            ],
+        )];
+        let clusters = DuplicateFinder::new(5.0).find(&groups);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(names(&clusters[0]), ["one", "near"]);
+        assert_eq!(clusters[0].keeper().index, 0);
+    }
+
+    #[test]
+    fn across_groups_unless_restricted() {
+        let groups = [
+            group("A", &[("a", 1, [0.0; 3])]),
+            group("B", &[("b", 1, [1.0, 0.0, 0.0])]),
+        ];
+        let clusters = DuplicateFinder::new(2.0).find(&groups);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].duplicates()[0].group, "B");
+        assert!(
+            DuplicateFinder::new(2.0)
+                .within_groups_only()
+                .find(&groups)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn chains_are_not_transitive() {
+        let groups = [group(
+            "A",
+            &[
+                ("a", 1, [0.0, 0.0, 0.0]),
+                ("b", 1, [4.0, 0.0, 0.0]),
+                ("c", 1, [8.0, 0.0, 0.0]),
+            ],
+        )];
+        let clusters = DuplicateFinder::new(5.0).find(&groups);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(names(&clusters[0]), ["a", "b"]);
+    }
+
+    #[test]
+    fn non_finite_coordinates_never_match() {
+        let nan = [f32::NAN, 0.0, 0.0];
+        let groups = [group("A", &[("a", 1, nan), ("b", 1, nan)])];
+        assert!(DuplicateFinder::new(100.0).find(&groups).is_empty());
+    }
+
+    #[test]
+    fn remove_drops_copies_and_merge_folds_tooltips() {
+        let mut base = group(
+            "A",
+            &[
+                ("a", 1, [0.0; 3]),
+                ("other", 2, [0.0; 3]),
+                ("a copy", 1, [0.5, 0.0, 0.0]),
+            ],
+        );
+        base.teleports[0].tooltip = Some("first".into());
+        base.teleports[2].tooltip = Some("second".into());
+        let clusters = DuplicateFinder::new(1.0).find(std::slice::from_ref(&base));
+
+        let mut removed = [base.clone()];
+        let report = dedupe_groups(&mut removed, &clusters, DedupeMode::Remove);
+        assert_eq!(
+            report,
+            DedupeReport {
+                clusters: 1,
+                removed: 1
+            }
+        );
+        assert_eq!(removed[0].teleports.len(), 2);
+        assert_eq!(removed[0].teleports[0].tooltip.as_deref(), Some("first"));
+
+        let mut merged = [base];
+        dedupe_groups(&mut merged, &clusters, DedupeMode::Merge);
+        let names: Vec<_> = merged[0]
+            .teleports
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, ["a", "other"]);
+        assert_eq!(
+            merged[0].teleports[0].tooltip.as_deref(),
+            Some("first\nsecond")
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::types::{Teleport, TeleportGroup};
+use crate::{Result, TeleportError};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Column headers of exported spreadsheets, in order
+pub const CSV_COLUMNS: [&str; 7] = ["group", "name", "map", "x", "y", "z", "tooltip"];
+
+/// A spreadsheet row that could not be imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRowError {
+    /// 1-based line number in the file
+    pub line: u64,
+    pub message: String,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Groups read from a spreadsheet along with the rows that were skipped
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvImport {
+    pub groups: Vec<TeleportGroup>,
+    pub errors: Vec<CsvRowError>,
+}
+
+impl CsvImport {
+    pub fn teleport_count(&self) -> usize {
+        self.groups.iter().map(|g| g.teleports.len()).sum()
+    }
+}
+
+/// Tab for `.tsv` files, comma for everything else
+pub fn csv_delimiter<P: AsRef<Path>>(path: P) -> u8 {
+    match path.as_ref().extension() {
+        Some(ext) if ext.eq_ignore_ascii_case("tsv") => b'\t',
+        _ => b',',
+    }
+}
+
+/// Write groups as one spreadsheet row per teleport, returning the number of rows
+pub fn write_csv<P: AsRef<Path>>(groups: &[TeleportGroup], path: P) -> Result<usize> {
+    if let Some(dir) = path.as_ref().parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(csv_delimiter(&path))
+        .from_path(&path)
+        .map_err(csv_error)?;
+
+    writer.write_record(CSV_COLUMNS).map_err(csv_error)?;
+    let mut rows = 0;
+    for group in groups {
+        for teleport in &group.teleports {
+            let [x, y, z] = teleport.coordinates;
+            writer
+                .write_record([
+                    group.name.clone(),
+                    teleport.name.clone(),
+                    teleport.map.to_string(),
+                    x.to_string(),
+                    y.to_string(),
+                    z.to_string(),
+                    teleport.tooltip.clone().unwrap_or_default(),
+                ])
+                .map_err(csv_error)?;
+            rows += 1;
+        }
+    }
+    writer.flush()?;
+    Ok(rows)
+}
+
+/// Read groups from a CSV or TSV file
+pub fn read_csv<P: AsRef<Path>>(path: P) -> Result<CsvImport> {
+    let data = fs::read_to_string(&path)?;
+    parse_csv(&data, csv_delimiter(&path))
+}
+
+/// Parse spreadsheet rows into groups, collecting bad rows instead of failing
+///
+/// The header row names the columns in any order. Rows are reported against
+/// any required column missing from the header, `tooltip` may be left out.
+pub fn parse_csv(data: &str, delimiter: u8) -> Result<CsvImport> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let mut columns: ColumnIndices = [None; CSV_COLUMNS.len()];
+    for (column, index) in CSV_COLUMNS.iter().zip(columns.iter_mut()) {
+        *index = headers.iter().position(|h| h.eq_ignore_ascii_case(column));
+    }
+    let mut import = CsvImport::default();
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                let message = e.to_string();
+                import.errors.push(CsvRowError { line, message });
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+
+        let row = parse_row(&record, &columns);
+        match row {
+            Ok((group, teleport)) => match import.groups.iter_mut().find(|g| g.name == group) {
+                Some(existing) => existing.teleports.push(teleport),
+                None => {
+                    let mut new_group = TeleportGroup::new(group);
+                    new_group.teleports.push(teleport);
+                    import.groups.push(new_group);
+                }
+            },
+            Err(message) => import.errors.push(CsvRowError { line, message }),
+        }
+    }
+    Ok(import)
+}
+
+/// Column indices of `CSV_COLUMNS` in the header, if present
+type ColumnIndices = [Option<usize>; CSV_COLUMNS.len()];
+
+fn parse_row(
+    record: &csv::StringRecord,
+    columns: &ColumnIndices,
+) -> std::result::Result<(String, Teleport), String> {
+    let field = |column: usize| {
+        let name = CSV_COLUMNS[column];
+        match columns[column].and_then(|i| record.get(i)) {
+            Some(value) if !value.is_empty() => Ok(value),
+            Some(_) => Err(format!("missing value for '{}'", name)),
+            None => Err(format!("missing column '{}'", name)),
+        }
+    };
+
+    let group = field(0)?;
+    let name = field(1)?;
+    let map = field(2)?;
+    let map = map
+        .parse::<u32>()
+        .map_err(|_| format!("map must be a map id, got '{}'", map))?;
+    let mut coordinates = [0.0f32; 3];
+    for (axis, value) in coordinates.iter_mut().enumerate() {
+        let input = field(3 + axis)?;
+        *value = input
+            .parse::<f32>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .ok_or_else(|| {
+                format!(
+                    "{} must be a number, got '{}'",
+                    CSV_COLUMNS[3 + axis],
+                    input
+                )
+            })?;
+    }
+
+    let mut teleport = Teleport::new(name, map, coordinates);
+    teleport.tooltip = field(6).ok().map(str::to_string);
+    Ok((group.to_string(), teleport))
+}
+
+fn csv_error(e: csv::Error) -> TeleportError {
+    TeleportError::IoError(e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rows_in_any_column_order() {
+        let data = "name,group,x,y,z,map,tooltip\n\
+                    Gate,Raids,1,2,3,1062,Start here\n\
+                    Boss,Raids,4.5,-5,6,1062,\n\
+                    \n\
+                    Arch,Cities,0,0,0,50,\n";
+        let import = parse_csv(data, b',').unwrap();
+        assert!(import.errors.is_empty());
+        assert_eq!(import.groups.len(), 2);
+        assert_eq!(import.teleport_count(), 3);
+
+        let raids = &import.groups[0];
+        assert_eq!(raids.name, "Raids");
+        assert_eq!(raids.teleports[0].tooltip.as_deref(), Some("Start here"));
+        assert_eq!(raids.teleports[1].coordinates, [4.5, -5.0, 6.0]);
+        assert_eq!(raids.teleports[1].tooltip, None);
+    }
+
+    #[test]
+    fn bad_rows_are_reported_by_line() {
+        let data = "group\tname\tmap\tx\ty\tz\n\
+                    Raids\tGate\t1062\t1\t2\t3\n\
+                    Raids\tBoss\tlion\t1\t2\t3\n\
+                    Raids\tWall\t1062\t1\tNaN\t3\n\
+                    Raids\t\t1062\t1\t2\t3\n\
+                    Raids\tShort\t1062\n";
+        let import = parse_csv(data, b'\t').unwrap();
+        assert_eq!(import.teleport_count(), 1);
+        let errors: Vec<_> = import.errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            errors,
+            [
+                "line 3: map must be a map id, got 'lion'",
+                "line 4: y must be a number, got 'NaN'",
+                "line 5: missing value for 'name'",
+                "line 6: missing column 'x'",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_header_columns_are_row_errors() {
+        let data = "group,name,map,x,y\nRaids,Gate,1062,1,2\nRaids,Boss,1062,3,4\n";
+        let import = parse_csv(data, b',').unwrap();
+        assert!(import.groups.is_empty());
+        assert_eq!(
+            import.errors,
+            [
+                CsvRowError {
+                    line: 2,
+                    message: "missing column 'z'".to_string()
+                },
+                CsvRowError {
+                    line: 3,
+                    message: "missing column 'z'".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn written_rows_read_back() {
+        let mut group = TeleportGroup::new("Raids, West");
+        let mut teleport = Teleport::new("Gate \"A\"", 1062, [1.25, -2.0, 3.0]);
+        teleport.tooltip = Some("Start".to_string());
+        group.teleports.push(teleport);
+        group
+            .teleports
+            .push(Teleport::new("Boss", 1062, [4.0, 5.0, 6.0]));
+
+        let path = std::env::temp_dir().join(format!("tp_sync_csv_{}.tsv", std::process::id()));
+        assert_eq!(write_csv(std::slice::from_ref(&group), &path).unwrap(), 2);
+        let import = read_csv(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(import.errors.is_empty());
+        assert_eq!(import.groups, [group]);
+    }
+}
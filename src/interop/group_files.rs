@@ -0,0 +1,69 @@
+use crate::Result;
+use crate::types::{TeleportConfig, TeleportGroup};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name a group is shared under, e.g. "Bjora Marches.json"
+///
+/// Characters that are not allowed in file names are replaced with '_'.
+pub fn group_file_name(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim_end_matches(['.', ' ']);
+    if stem.is_empty() {
+        "group.json".to_string()
+    } else {
+        format!("{}.json", stem)
+    }
+}
+
+/// Write a group as a standalone file in the format remote providers serve
+pub fn write_group_file<P: AsRef<Path>>(group: &TeleportGroup, dir: P) -> Result<PathBuf> {
+    fs::create_dir_all(&dir)?;
+    let path = dir.as_ref().join(group_file_name(&group.name));
+    let json = serde_json::to_string_pretty(group)?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Write each group as its own standalone file, returning the written paths
+pub fn write_group_files<P: AsRef<Path>>(groups: &[TeleportGroup], dir: P) -> Result<Vec<PathBuf>> {
+    groups
+        .iter()
+        .map(|group| write_group_file(group, &dir))
+        .collect()
+}
+
+/// Read a standalone group file
+pub fn read_group_file<P: AsRef<Path>>(path: P) -> Result<TeleportGroup> {
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Write several groups into one bundle file with a `teleport_groups` list
+pub fn write_bundle<P: AsRef<Path>>(groups: &[TeleportGroup], path: P) -> Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bundle = TeleportConfig {
+        teleport_groups: groups.to_vec(),
+        ..TeleportConfig::default()
+    };
+    let json = serde_json::to_string_pretty(&bundle)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Read the groups of a bundle file
+pub fn read_bundle<P: AsRef<Path>>(path: P) -> Result<Vec<TeleportGroup>> {
+    let content = fs::read_to_string(path)?;
+    let bundle: TeleportConfig = serde_json::from_str(&content)?;
+    Ok(bundle.teleport_groups)
+}
@@ -0,0 +1,460 @@
+use crate::types::{Teleport, TeleportGroup};
+use crate::{Result, TeleportError};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// A marker category, e.g. `tyria.waypoints` shown as "Tyria / Waypoints"
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkerCategory {
+    /// Lowercase dotted category path as used by the POI `type` attribute
+    pub path: String,
+    /// Display names of the category and its parents joined with " / "
+    pub display_name: String,
+}
+
+/// A single POI read from a marker pack
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkerPoi {
+    /// Lowercase dotted category path
+    pub category: String,
+    pub map: u32,
+    /// `[xpos, ypos, zpos]` as stored in the pack
+    pub position: [f32; 3],
+    pub name: Option<String>,
+    pub tooltip: Option<String>,
+}
+
+/// TacO / Blish HUD marker pack contents
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkerPack {
+    pub categories: Vec<MarkerCategory>,
+    pub pois: Vec<MarkerPoi>,
+}
+
+impl MarkerPack {
+    /// Parse a single marker pack XML document
+    pub fn parse(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        let mut pack = MarkerPack::default();
+        // Category paths of the currently open MarkerCategory elements
+        let mut stack: Vec<(String, String)> = Vec::new();
+
+        loop {
+            let event = reader.read_event().map_err(|e| {
+                TeleportError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "Failed to parse marker pack at byte {}: {}",
+                        reader.error_position(),
+                        e
+                    ),
+                ))
+            })?;
+
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let is_empty = matches!(event, Event::Empty(_));
+                    let tag = element_name(e);
+                    if tag == "markercategory" {
+                        let category = pack.push_category(e, stack.last());
+                        if !is_empty {
+                            stack.push(category);
+                        }
+                    } else if tag == "poi" {
+                        pack.push_poi(e);
+                    }
+                }
+                Event::End(ref e) if e.name().as_ref().eq_ignore_ascii_case("markercategory") => {
+                    stack.pop();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(pack)
+    }
+
+    /// Load a marker pack from an XML file or a directory of XML files
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Self::parse(&fs::read_to_string(path)?);
+        }
+
+        let mut files: Vec<_> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+            })
+            .collect();
+        files.sort();
+
+        // Categories and POIs may live in different files, so collect everything first
+        let mut pack = MarkerPack::default();
+        for file in files {
+            let part = Self::parse(&fs::read_to_string(&file)?)?;
+            for category in part.categories {
+                if pack.category(&category.path).is_none() {
+                    pack.categories.push(category);
+                }
+            }
+            pack.pois.extend(part.pois);
+        }
+        Ok(pack)
+    }
+
+    /// Look up a category by its dotted path, ignoring case
+    pub fn category(&self, path: &str) -> Option<&MarkerCategory> {
+        let path = path.to_ascii_lowercase();
+        self.categories.iter().find(|c| c.path == path)
+    }
+
+    /// Convert every category that has POIs of its own into a teleport group
+    ///
+    /// Group names are unique, clashing display names get the category path appended.
+    pub fn to_groups(&self) -> Vec<TeleportGroup> {
+        let mut paths: Vec<&str> = Vec::new();
+        for poi in &self.pois {
+            if !paths.contains(&poi.category.as_str()) {
+                paths.push(&poi.category);
+            }
+        }
+
+        let mut groups: Vec<TeleportGroup> = paths
+            .iter()
+            .map(|path| self.build_group(path, |category| category == *path))
+            .collect();
+
+        // Categories in different places can share a display name, add the path to tell them apart
+        let names: Vec<String> = groups.iter().map(|g| g.name.clone()).collect();
+        for (group, path) in groups.iter_mut().zip(paths) {
+            if names.iter().filter(|name| **name == group.name).count() > 1 {
+                group.name = format!("{} ({})", group.name, path);
+            }
+        }
+        groups
+    }
+
+    /// Convert a category and all of its subcategories into a single teleport group
+    pub fn category_group(&self, path: &str) -> Option<TeleportGroup> {
+        let path = path.to_ascii_lowercase();
+        let prefix = format!("{}.", path);
+        let group = self.build_group(&path, |category| {
+            category == path || category.starts_with(&prefix)
+        });
+        (!group.teleports.is_empty()).then_some(group)
+    }
+
+    /// Build a marker pack with one top-level category per group
+    pub fn from_groups(groups: &[TeleportGroup]) -> Self {
+        let mut pack = MarkerPack::default();
+        for group in groups {
+            let base = category_name(&group.name);
+            let mut path = base.clone();
+            let mut n = 2;
+            while pack.category(&path).is_some() {
+                path = format!("{}_{}", base, n);
+                n += 1;
+            }
+
+            pack.categories.push(MarkerCategory {
+                path: path.clone(),
+                display_name: group.name.clone(),
+            });
+            pack.pois
+                .extend(group.teleports.iter().map(|teleport| MarkerPoi {
+                    category: path.clone(),
+                    map: teleport.map,
+                    position: teleport.coordinates,
+                    name: Some(teleport.name.clone()),
+                    tooltip: teleport.tooltip.clone(),
+                }));
+        }
+        pack
+    }
+
+    /// Render the pack as marker pack XML
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<OverlayData>\n");
+        for category in self.categories.iter().filter(|c| !c.path.contains('.')) {
+            self.write_category(&mut xml, category, "", 1);
+        }
+
+        xml.push_str("  <POIs>\n");
+        for poi in &self.pois {
+            let [x, y, z] = poi.position;
+            let _ = write!(
+                xml,
+                "    <POI MapID=\"{}\" xpos=\"{}\" ypos=\"{}\" zpos=\"{}\" type=\"{}\"",
+                poi.map,
+                x,
+                y,
+                z,
+                escape(poi.category.as_str())
+            );
+            if let Some(name) = &poi.name {
+                let _ = write!(xml, " tip-name=\"{}\"", escape(name.as_str()));
+            }
+            if let Some(tooltip) = &poi.tooltip {
+                let _ = write!(xml, " tip-description=\"{}\"", escape(tooltip.as_str()));
+            }
+            xml.push_str(" />\n");
+        }
+        xml.push_str("  </POIs>\n</OverlayData>\n");
+        xml
+    }
+
+    /// Write the pack as a marker pack XML file
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_xml())?;
+        Ok(())
+    }
+
+    fn write_category(
+        &self,
+        xml: &mut String,
+        category: &MarkerCategory,
+        parent_display: &str,
+        depth: usize,
+    ) {
+        let indent = "  ".repeat(depth);
+        let name = category.path.rsplit('.').next().unwrap_or(&category.path);
+        let display = category
+            .display_name
+            .strip_prefix(parent_display)
+            .and_then(|rest| rest.strip_prefix(" / "))
+            .unwrap_or(&category.display_name);
+        let _ = write!(
+            xml,
+            "{}<MarkerCategory name=\"{}\" DisplayName=\"{}\"",
+            indent,
+            escape(name),
+            escape(display)
+        );
+
+        let prefix = format!("{}.", category.path);
+        let children: Vec<&MarkerCategory> = self
+            .categories
+            .iter()
+            .filter(|c| {
+                c.path
+                    .strip_prefix(&prefix)
+                    .is_some_and(|rest| !rest.contains('.'))
+            })
+            .collect();
+        if children.is_empty() {
+            xml.push_str(" />\n");
+            return;
+        }
+
+        xml.push_str(">\n");
+        for child in children {
+            self.write_category(xml, child, &category.display_name, depth + 1);
+        }
+        let _ = writeln!(xml, "{}</MarkerCategory>", indent);
+    }
+
+    fn build_group(&self, path: &str, include: impl Fn(&str) -> bool) -> TeleportGroup {
+        let group_name = self.display_name(path);
+        let mut group = TeleportGroup::new(group_name.clone());
+
+        for poi in self.pois.iter().filter(|p| include(&p.category)) {
+            let name = poi.name.clone().unwrap_or_else(|| {
+                let category = self.display_name(&poi.category);
+                let short = category.rsplit(" / ").next().unwrap_or(&category);
+                format!("{} {}", short, group.teleports.len() + 1)
+            });
+            let mut teleport = Teleport::new(name, poi.map, poi.position);
+            teleport.tooltip = poi.tooltip.clone();
+            group.teleports.push(teleport);
+        }
+        group
+    }
+
+    /// Display name of a category, falling back to its dotted path
+    fn display_name(&self, path: &str) -> String {
+        match self.category(path) {
+            Some(category) => category.display_name.clone(),
+            None if path.is_empty() => "Markers".to_string(),
+            None => path.to_string(),
+        }
+    }
+
+    fn push_category(
+        &mut self,
+        e: &BytesStart,
+        parent: Option<&(String, String)>,
+    ) -> (String, String) {
+        let name = attribute(e, "name")
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let display = attribute(e, "displayname").unwrap_or_else(|| name.clone());
+
+        let (path, display_name) = match parent {
+            Some((parent_path, parent_display)) => (
+                format!("{}.{}", parent_path, name),
+                format!("{} / {}", parent_display, display),
+            ),
+            None => (name, display),
+        };
+
+        if self.category(&path).is_none() {
+            self.categories.push(MarkerCategory {
+                path: path.clone(),
+                display_name: display_name.clone(),
+            });
+        }
+        (path, display_name)
+    }
+
+    fn push_poi(&mut self, e: &BytesStart) {
+        let Some(map) = attribute(e, "mapid").and_then(|v| v.trim().parse().ok()) else {
+            return;
+        };
+        let coordinate = |key: &str| {
+            attribute(e, key)
+                .and_then(|v| v.trim().parse::<f32>().ok())
+                .unwrap_or_default()
+        };
+
+        self.pois.push(MarkerPoi {
+            category: attribute(e, "type")
+                .unwrap_or_default()
+                .to_ascii_lowercase(),
+            map,
+            position: [coordinate("xpos"), coordinate("ypos"), coordinate("zpos")],
+            name: attribute(e, "tip-name").filter(|s| !s.is_empty()),
+            tooltip: attribute(e, "tip-description")
+                .or_else(|| attribute(e, "info"))
+                .filter(|s| !s.is_empty()),
+        });
+    }
+}
+
+/// Category name for a group, e.g. "Bjora Marches" becomes "bjora_marches"
+fn category_name(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_end_matches('_');
+    if slug.is_empty() {
+        "group".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Lowercase local name of an element
+fn element_name(e: &BytesStart) -> String {
+    e.local_name().as_ref().to_ascii_lowercase()
+}
+
+/// Value of an attribute, matching its name case-insensitively
+fn attribute(e: &BytesStart, key: &str) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref().eq_ignore_ascii_case(key))
+        .and_then(|a| a.normalized_value(XmlVersion::Implicit1_0).ok())
+        .map(|v| v.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"<OverlayData>
+  <MarkerCategory name="Tyria" DisplayName="Tyria">
+    <MarkerCategory name="Waypoints" DisplayName="Waypoints" />
+    <MarkerCategory name="Vistas" DisplayName="Vistas" />
+  </MarkerCategory>
+  <MarkerCategory name="Maguuma" DisplayName="Maguuma">
+    <MarkerCategory name="Waypoints" DisplayName="Waypoints" />
+  </MarkerCategory>
+  <MarkerCategory name="Extra" DisplayName="Tyria / Waypoints" />
+  <POIs>
+    <POI MapID="15" xpos="1.5" ypos="2" zpos="-3" type="tyria.waypoints" tip-name="Shaemoor" tip-description="Start" />
+    <POI MapID="15" xpos="4" ypos="5" zpos="6" type="Tyria.Vistas" />
+    <POI MapID="1052" xpos="7" ypos="8" zpos="9" type="maguuma.waypoints" tip-name="Jaka Itzel" />
+    <POI MapID="20" xpos="0" ypos="0" zpos="0" type="extra" />
+    <POI xpos="1" ypos="1" zpos="1" type="tyria.waypoints" />
+  </POIs>
+</OverlayData>"#;
+
+    #[test]
+    fn parses_categories_and_pois() {
+        let pack = MarkerPack::parse(PACK).unwrap();
+        assert_eq!(
+            pack.category("TYRIA.WAYPOINTS").unwrap().display_name,
+            "Tyria / Waypoints"
+        );
+        // The POI without a MapID is skipped
+        assert_eq!(pack.pois.len(), 4);
+        assert_eq!(pack.pois[0].position, [1.5, 2.0, -3.0]);
+        assert_eq!(pack.pois[1].category, "tyria.vistas");
+        assert_eq!(pack.pois[0].tooltip.as_deref(), Some("Start"));
+    }
+
+    #[test]
+    fn group_names_are_unique() {
+        let groups = MarkerPack::parse(PACK).unwrap().to_groups();
+        let names: Vec<_> = groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Tyria / Waypoints (tyria.waypoints)",
+                "Tyria / Vistas",
+                "Maguuma / Waypoints",
+                "Tyria / Waypoints (extra)",
+            ]
+        );
+        // Unnamed POIs are numbered after their category
+        assert_eq!(groups[1].teleports[0].name, "Vistas 1");
+    }
+
+    #[test]
+    fn category_group_includes_subcategories() {
+        let pack = MarkerPack::parse(PACK).unwrap();
+        let group = pack.category_group("Tyria").unwrap();
+        assert_eq!(group.name, "Tyria");
+        assert_eq!(group.teleports.len(), 2);
+        assert!(pack.category_group("tyria.missing").is_none());
+    }
+
+    #[test]
+    fn several_groups_round_trip() {
+        let mut raids = TeleportGroup::new("Raids");
+        raids.teleports.push(crate::types::Teleport::new(
+            "Vale Guardian",
+            1062,
+            [1.0, 2.0, 3.0],
+        ));
+        let mut maps = TeleportGroup::new("Raids!");
+        maps.teleports.push(crate::types::Teleport::new(
+            "Lion's Arch",
+            50,
+            [4.0, 5.0, 6.0],
+        ));
+
+        let xml = MarkerPack::from_groups(&[raids, maps]).to_xml();
+        let groups = MarkerPack::parse(&xml).unwrap().to_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].name, "Raids");
+        assert_eq!(groups[0].teleports[0].map, 1062);
+        assert_eq!(groups[1].name, "Raids!");
+        assert_eq!(groups[1].teleports[0].coordinates, [4.0, 5.0, 6.0]);
+    }
+}
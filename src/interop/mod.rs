@@ -0,0 +1,12 @@
+mod csv_files;
+mod group_files;
+mod marker_pack;
+
+pub use csv_files::{
+    CSV_COLUMNS, CsvImport, CsvRowError, csv_delimiter, parse_csv, read_csv, write_csv,
+};
+pub use group_files::{
+    group_file_name, read_bundle, read_group_file, write_bundle, write_group_file,
+    write_group_files,
+};
+pub use marker_pack::{MarkerCategory, MarkerPack, MarkerPoi};
@@ -0,0 +1,682 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Something a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    SwitchPane,
+    Up,
+    Down,
+    MoveUp,
+    MoveDown,
+    Install,
+    Delete,
+    Details,
+    Diff,
+    Reload,
+    Search,
+    ClearSearch,
+    Mark,
+    MarkAll,
+    InvertMarks,
+    Dedupe,
+    Edit,
+    Pick,
+    Duplicate,
+    Split,
+    SplitPick,
+    Merge,
+    Export,
+    ExportBundle,
+    ExportMarkerPack,
+    ExportCsv,
+    ImportCsv,
+    ToggleLog,
+    ScrollLogUp,
+    ScrollLogDown,
+    Help,
+    /// Answer yes in a confirmation dialog
+    Confirm,
+    /// Answer no in a confirmation dialog
+    Cancel,
+    /// Previous option in a dialog or target in the picker
+    PrevChoice,
+    /// Next option in a dialog or target in the picker
+    NextChoice,
+    /// Apply the chosen option of a dialog
+    ApplyChoice,
+    EditorAdd,
+    EditorEdit,
+    EditorDelete,
+    EditorMoveUp,
+    EditorMoveDown,
+    EditorSave,
+    EditorDiscard,
+    PickerToggle,
+    PickerToggleAll,
+    PickerInstall,
+    PickerCancel,
+    /// Keep the search filter and stop typing
+    SearchDone,
+}
+
+/// Where a key is pressed, each place with its own actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScope {
+    /// The two panes
+    Main,
+    /// Confirmation dialogs
+    Dialog,
+    /// The group editor, outside of the teleport form
+    Editor,
+    /// The teleport picker
+    Picker,
+    /// Typing a search
+    Search,
+}
+
+impl KeyScope {
+    pub const ALL: [KeyScope; 5] = [
+        KeyScope::Main,
+        KeyScope::Dialog,
+        KeyScope::Editor,
+        KeyScope::Picker,
+        KeyScope::Search,
+    ];
+
+    /// Actions a key can trigger here, in lookup order
+    ///
+    /// The help key opens the overlay everywhere but while typing.
+    pub fn actions(self) -> &'static [Action] {
+        match self {
+            // Every action up to and including Help
+            KeyScope::Main => &Action::ALL[..32],
+            KeyScope::Dialog => &[
+                Action::Confirm,
+                Action::Cancel,
+                Action::PrevChoice,
+                Action::NextChoice,
+                Action::ApplyChoice,
+                Action::Help,
+            ],
+            KeyScope::Editor => &[
+                Action::EditorMoveUp,
+                Action::EditorMoveDown,
+                Action::Up,
+                Action::Down,
+                Action::EditorAdd,
+                Action::EditorEdit,
+                Action::EditorDelete,
+                Action::EditorSave,
+                Action::EditorDiscard,
+                Action::Help,
+            ],
+            KeyScope::Picker => &[
+                Action::Up,
+                Action::Down,
+                Action::PrevChoice,
+                Action::NextChoice,
+                Action::PickerToggle,
+                Action::PickerToggleAll,
+                Action::PickerInstall,
+                Action::PickerCancel,
+                Action::Help,
+            ],
+            KeyScope::Search => &[
+                Action::Up,
+                Action::Down,
+                Action::SwitchPane,
+                Action::SearchDone,
+                Action::ClearSearch,
+            ],
+        }
+    }
+}
+
+impl Action {
+    pub const ALL: [Action; 49] = [
+        Action::Quit,
+        Action::SwitchPane,
+        Action::Up,
+        Action::Down,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::Install,
+        Action::Delete,
+        Action::Details,
+        Action::Diff,
+        Action::Reload,
+        Action::Search,
+        Action::ClearSearch,
+        Action::Mark,
+        Action::MarkAll,
+        Action::InvertMarks,
+        Action::Dedupe,
+        Action::Edit,
+        Action::Pick,
+        Action::Duplicate,
+        Action::Split,
+        Action::SplitPick,
+        Action::Merge,
+        Action::Export,
+        Action::ExportBundle,
+        Action::ExportMarkerPack,
+        Action::ExportCsv,
+        Action::ImportCsv,
+        Action::ToggleLog,
+        Action::ScrollLogUp,
+        Action::ScrollLogDown,
+        Action::Help,
+        Action::Confirm,
+        Action::Cancel,
+        Action::PrevChoice,
+        Action::NextChoice,
+        Action::ApplyChoice,
+        Action::EditorAdd,
+        Action::EditorEdit,
+        Action::EditorDelete,
+        Action::EditorMoveUp,
+        Action::EditorMoveDown,
+        Action::EditorSave,
+        Action::EditorDiscard,
+        Action::PickerToggle,
+        Action::PickerToggleAll,
+        Action::PickerInstall,
+        Action::PickerCancel,
+        Action::SearchDone,
+    ];
+
+    /// Name used for the action in config.toml
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::SwitchPane => "switch_pane",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::Install => "install",
+            Action::Delete => "delete",
+            Action::Details => "details",
+            Action::Diff => "diff",
+            Action::Reload => "reload",
+            Action::Search => "search",
+            Action::ClearSearch => "clear_search",
+            Action::Mark => "mark",
+            Action::MarkAll => "mark_all",
+            Action::InvertMarks => "invert_marks",
+            Action::Dedupe => "dedupe",
+            Action::Edit => "edit",
+            Action::Pick => "pick",
+            Action::Duplicate => "duplicate",
+            Action::Split => "split",
+            Action::SplitPick => "split_pick",
+            Action::Merge => "merge",
+            Action::Export => "export",
+            Action::ExportBundle => "export_bundle",
+            Action::ExportMarkerPack => "export_marker_pack",
+            Action::ExportCsv => "export_csv",
+            Action::ImportCsv => "import_csv",
+            Action::ToggleLog => "toggle_log",
+            Action::ScrollLogUp => "scroll_log_up",
+            Action::ScrollLogDown => "scroll_log_down",
+            Action::Help => "help",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::PrevChoice => "prev_choice",
+            Action::NextChoice => "next_choice",
+            Action::ApplyChoice => "apply_choice",
+            Action::EditorAdd => "editor_add",
+            Action::EditorEdit => "editor_edit",
+            Action::EditorDelete => "editor_delete",
+            Action::EditorMoveUp => "editor_move_up",
+            Action::EditorMoveDown => "editor_move_down",
+            Action::EditorSave => "editor_save",
+            Action::EditorDiscard => "editor_discard",
+            Action::PickerToggle => "picker_toggle",
+            Action::PickerToggleAll => "picker_toggle_all",
+            Action::PickerInstall => "picker_install",
+            Action::PickerCancel => "picker_cancel",
+            Action::SearchDone => "search_done",
+        }
+    }
+
+    /// What the action does, for the help overlay
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::SwitchPane => "Switch between the local and remote pane",
+            Action::Up => "Select the previous group",
+            Action::Down => "Select the next group",
+            Action::MoveUp => "Move the group up in teleport.json",
+            Action::MoveDown => "Move the group down in teleport.json",
+            Action::Install => "Install the selected or marked groups",
+            Action::Delete => "Delete the selected or marked groups",
+            Action::Details => "Show the group's teleports",
+            Action::Diff => "Compare the group with its remote version",
+            Action::Reload => "Reload the remote group list",
+            Action::Search => "Filter both panes by name",
+            Action::ClearSearch => "Clear the search filter",
+            Action::Mark => "Mark or unmark the group",
+            Action::MarkAll => "Mark every shown group",
+            Action::InvertMarks => "Invert the marks",
+            Action::Dedupe => "Merge duplicate teleports",
+            Action::Edit => "Edit the group's teleports",
+            Action::Pick => "Install individual teleports from the group",
+            Action::Duplicate => "Duplicate the group",
+            Action::Split => "Split the group into one group per map",
+            Action::SplitPick => "Split chosen teleports into a new group",
+            Action::Merge => "Merge the marked groups, or the group, into one",
+            Action::Export => "Export the marked groups, or the group, as shareable JSON files",
+            Action::ExportBundle => {
+                "Export the marked local groups, or the selected one, into bundle.json"
+            }
+            Action::ExportMarkerPack => "Export the marked groups, or the group, as a marker pack",
+            Action::ExportCsv => "Export all local teleports to teleports.csv",
+            Action::ImportCsv => "Import teleports from a CSV or TSV file",
+            Action::ToggleLog => "Show or hide the activity log",
+            Action::ScrollLogUp => "Scroll the activity log to older entries",
+            Action::ScrollLogDown => "Scroll the activity log to newer entries",
+            Action::Help => "Show this help",
+            Action::Confirm => "Confirm",
+            Action::Cancel => "Cancel, or close the details, diff or help",
+            Action::PrevChoice => "Previous option",
+            Action::NextChoice => "Next option",
+            Action::ApplyChoice => "Apply the chosen option",
+            Action::EditorAdd => "Add a teleport",
+            Action::EditorEdit => "Edit the teleport",
+            Action::EditorDelete => "Delete the teleport",
+            Action::EditorMoveUp => "Move the teleport up",
+            Action::EditorMoveDown => "Move the teleport down",
+            Action::EditorSave => "Save the group",
+            Action::EditorDiscard => "Discard changes",
+            Action::PickerToggle => "Toggle the teleport",
+            Action::PickerToggleAll => "Select all or none",
+            Action::PickerInstall => "Install the selection",
+            Action::PickerCancel => "Cancel",
+            Action::SearchDone => "Keep the filter",
+        }
+    }
+
+    /// Keys bound to the action unless config.toml overrides them
+    pub fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::SwitchPane => &["Tab"],
+            Action::Up => &["Up"],
+            Action::Down => &["Down"],
+            Action::MoveUp => &["Shift+Up"],
+            Action::MoveDown => &["Shift+Down"],
+            Action::Install => &["Space"],
+            Action::Delete => &["Delete"],
+            Action::Details => &["Enter"],
+            Action::Diff => &["D"],
+            Action::Reload => &["r"],
+            Action::Search => &["/"],
+            Action::ClearSearch => &["Esc"],
+            Action::Mark => &["m", "Insert"],
+            Action::MarkAll => &["A"],
+            Action::InvertMarks => &["*"],
+            Action::Dedupe => &["d"],
+            Action::Edit => &["e"],
+            Action::Pick => &["p"],
+            Action::Duplicate => &["c"],
+            Action::Split => &["S"],
+            Action::SplitPick => &["x"],
+            Action::Merge => &["M"],
+            Action::Export => &["E"],
+            Action::ExportBundle => &["B"],
+            Action::ExportMarkerPack => &["T"],
+            Action::ExportCsv => &["C"],
+            Action::ImportCsv => &["I"],
+            Action::ToggleLog => &["L"],
+            Action::ScrollLogUp => &["PageUp"],
+            Action::ScrollLogDown => &["PageDown"],
+            Action::Help => &["?"],
+            Action::Confirm => &["y", "Y"],
+            Action::Cancel => &["n", "N", "Esc"],
+            Action::PrevChoice => &["Left"],
+            Action::NextChoice => &["Right"],
+            Action::ApplyChoice => &["Enter"],
+            Action::EditorAdd => &["a"],
+            Action::EditorEdit => &["e", "Enter"],
+            Action::EditorDelete => &["Delete"],
+            Action::EditorMoveUp => &["Shift+Up"],
+            Action::EditorMoveDown => &["Shift+Down"],
+            Action::EditorSave => &["s"],
+            Action::EditorDiscard => &["Esc"],
+            Action::PickerToggle => &["Space"],
+            Action::PickerToggleAll => &["a"],
+            Action::PickerInstall => &["Enter"],
+            Action::PickerCancel => &["Esc"],
+            Action::SearchDone => &["Enter"],
+        }
+    }
+}
+
+/// A key with its modifiers, written like `q`, `Space`, `Shift+Up` or `Ctrl+d`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Whether a pressed key triggers this binding
+    ///
+    /// Shift is ignored for characters, since terminals disagree on
+    /// reporting it alongside the already uppercase character.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = match self.code {
+            KeyCode::Char(_) => KeyModifiers::CONTROL | KeyModifiers::ALT,
+            _ => KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+        };
+        self.code == key.code && self.modifiers & relevant == key.modifiers & relevant
+    }
+
+    /// A key event that triggers this binding
+    pub fn to_event(self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+}
+
+/// Keys that can't be rebound, written like bound keys, e.g. `Tab/Down`
+pub fn fixed_label(codes: &[KeyCode]) -> String {
+    codes
+        .iter()
+        .map(|code| KeyBinding::from(*code).to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s;
+        // A trailing "+" is the plus key itself, as in "Ctrl++"
+        while let Some((modifier, rest)) = key.split_once('+').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "shift" => KeyModifiers::SHIFT,
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(format!("unknown modifier '{}' in '{}'", modifier, s)),
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            // Terminals report the shifted character, which Shift+ can't name
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                return Err(format!(
+                    "'{}' can't be typed, write the shifted character instead, like '{}'",
+                    s,
+                    c.to_uppercase()
+                ));
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", s)),
+                },
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::PageUp => f.write_str("PageUp"),
+            KeyCode::PageDown => f.write_str("PageDown"),
+            KeyCode::BackTab => f.write_str("BackTab"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+impl Serialize for KeyBinding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyBinding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Keys bound to each action, the `[keymap]` table of config.toml
+///
+/// Only actions listed in the config are stored; every other action keeps its default keys.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap {
+    overrides: BTreeMap<Action, Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn is_default(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Keys bound to an action
+    pub fn bindings(&self, action: Action) -> Vec<KeyBinding> {
+        match self.overrides.get(&action) {
+            Some(keys) => keys.clone(),
+            None => action
+                .default_keys()
+                .iter()
+                .map(|key| key.parse().expect("default key bindings are valid"))
+                .collect(),
+        }
+    }
+
+    /// The main screen action a pressed key triggers
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        self.action_in(KeyScope::Main, key)
+    }
+
+    /// The action a pressed key triggers in a scope
+    pub fn action_in(&self, scope: KeyScope, key: &KeyEvent) -> Option<Action> {
+        scope
+            .actions()
+            .iter()
+            .copied()
+            .find(|action| self.bindings(*action).iter().any(|b| b.matches(key)))
+    }
+
+    /// Bound keys of an action for hints, like `m/Insert`
+    pub fn label(&self, action: Action) -> String {
+        self.bindings(action)
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Check that every action has a key and no key triggers two actions in one scope
+    pub fn validate(&self) -> crate::Result<()> {
+        for action in Action::ALL {
+            if self.bindings(action).is_empty() {
+                return Err(crate::TeleportError::Config(format!(
+                    "keymap: '{}' has no keys bound",
+                    action.name()
+                )));
+            }
+        }
+
+        for scope in KeyScope::ALL {
+            let actions = scope.actions();
+            for (i, a) in actions.iter().enumerate() {
+                for b in &actions[i + 1..] {
+                    self.check_unique(*a, *b)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_unique(&self, a: Action, b: Action) -> crate::Result<()> {
+        for key in self.bindings(a) {
+            if self
+                .bindings(b)
+                .iter()
+                .any(|other| other.matches(&key.to_event()))
+            {
+                return Err(crate::TeleportError::Config(format!(
+                    "keymap: '{}' is bound to both '{}' and '{}'",
+                    key,
+                    a.name(),
+                    b.name()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> KeyBinding {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn bindings_round_trip() {
+        for key in [
+            "q",
+            "Ctrl++",
+            "Alt+Enter",
+            "Shift+Up",
+            "F12",
+            "Space",
+            "PageDown",
+            "*",
+        ] {
+            assert_eq!(parse(key).to_string(), key);
+        }
+        assert_eq!(parse("Ctrl++").code, KeyCode::Char('+'));
+        assert_eq!(parse("Ctrl++").modifiers, KeyModifiers::CONTROL);
+        assert_eq!(parse("f12").code, KeyCode::F(12));
+        assert_eq!(parse("control+Del").to_string(), "Ctrl+Delete");
+        assert_eq!(fixed_label(&[KeyCode::Tab, KeyCode::Down]), "Tab/Down");
+        assert_eq!(fixed_label(&[KeyCode::Esc]), "Esc");
+    }
+
+    #[test]
+    fn unknown_and_untypeable_keys_are_rejected() {
+        assert!("F13".parse::<KeyBinding>().is_err());
+        assert!("Hyper+q".parse::<KeyBinding>().is_err());
+        assert!("Launch".parse::<KeyBinding>().is_err());
+        let error = "Shift+a".parse::<KeyBinding>().unwrap_err();
+        assert!(error.contains("'A'"), "{}", error);
+    }
+
+    #[test]
+    fn default_keymap_is_valid() {
+        assert!(Keymap::default().validate().is_ok());
+        assert_eq!(KeyScope::Main.actions().last(), Some(&Action::Help));
+        for action in Action::ALL {
+            assert!(!Keymap::default().bindings(action).is_empty());
+        }
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected_within_a_scope() {
+        let keymap: Keymap = toml::from_str(r#"delete = ["q"]"#).unwrap();
+        let error = keymap.validate().unwrap_err().to_string();
+        assert!(
+            error.contains("'quit'") && error.contains("'delete'"),
+            "{}",
+            error
+        );
+
+        let keymap: Keymap = toml::from_str(r#"editor_save = ["Shift+Up"]"#).unwrap();
+        assert!(keymap.validate().is_err());
+
+        // The same key may do different things in different places
+        let keymap: Keymap = toml::from_str(r#"editor_save = ["q"]"#).unwrap();
+        assert!(keymap.validate().is_ok());
+
+        let keymap: Keymap = toml::from_str("cancel = []").unwrap();
+        assert!(keymap.validate().is_err());
+    }
+
+    #[test]
+    fn keys_resolve_per_scope() {
+        let keymap = Keymap::default();
+        let enter = KeyEvent::from(KeyCode::Enter);
+        assert_eq!(keymap.action(&enter), Some(Action::Details));
+        assert_eq!(
+            keymap.action_in(KeyScope::Editor, &enter),
+            Some(Action::EditorEdit)
+        );
+        assert_eq!(
+            keymap.action_in(KeyScope::Picker, &enter),
+            Some(Action::PickerInstall)
+        );
+        assert_eq!(
+            keymap.action_in(KeyScope::Dialog, &enter),
+            Some(Action::ApplyChoice)
+        );
+        let shift_up = KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(
+            keymap.action_in(KeyScope::Editor, &shift_up),
+            Some(Action::EditorMoveUp)
+        );
+    }
+}
@@ -1,13 +1,23 @@
 use std::io;
+use std::time::SystemTime;
 
+pub mod activity;
 pub mod config;
+pub mod diff;
+pub mod digest;
+pub mod duplicates;
+pub mod interop;
+pub mod keymap;
 mod manager;
+pub mod maps;
 pub mod remote;
 pub mod sync;
+pub mod theme;
 mod types;
 pub mod ui;
+pub mod validation;
 
-pub use manager::TeleportManager;
+pub use manager::{ConflictPolicy, InstallOutcome, TeleportManager};
 pub use types::*;
 
 /// Custom error type for teleport operations
@@ -17,6 +27,38 @@ pub enum TeleportError {
     JsonError(serde_json::Error),
     GroupNotFound(String),
     GroupAlreadyExists(String),
+    /// The remote answered with a non-success status
+    HttpStatus {
+        status: u16,
+        url: String,
+    },
+    /// The remote could not be reached, e.g. when offline
+    Transport {
+        url: String,
+        source: reqwest::Error,
+    },
+    /// A remote group, or the remote group list if `group` is None, is not valid JSON
+    RemoteParse {
+        group: Option<String>,
+        source: serde_json::Error,
+    },
+    /// config.toml could not be read, written or validated
+    Config(String),
+    /// The remote refuses requests until the rate limit resets
+    RateLimited {
+        reset_at: Option<SystemTime>,
+    },
+}
+
+impl TeleportError {
+    /// Whether retrying the same request later may succeed
+    pub fn is_transient(&self) -> bool {
+        match self {
+            TeleportError::Transport { .. } | TeleportError::RateLimited { .. } => true,
+            TeleportError::HttpStatus { status, .. } => *status == 408 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for TeleportError {
@@ -28,11 +70,68 @@ impl std::fmt::Display for TeleportError {
             TeleportError::GroupAlreadyExists(name) => {
                 write!(f, "Group '{}' is already installed", name)
             }
+            TeleportError::HttpStatus { status, url } => {
+                let reason = reqwest::StatusCode::from_u16(*status)
+                    .ok()
+                    .and_then(|s| s.canonical_reason())
+                    .unwrap_or("Unknown status");
+                write!(f, "HTTP {} {} from {}", status, reason, url)
+            }
+            TeleportError::Transport { url, source } if source.is_timeout() => {
+                write!(f, "Timed out reaching {} ({})", url, error_chain(source))
+            }
+            TeleportError::Transport { url, source } => write!(
+                f,
+                "Could not reach {}, check your connection ({})",
+                url,
+                error_chain(source)
+            ),
+            TeleportError::RemoteParse {
+                group: Some(group),
+                source,
+            } => write!(f, "Remote group '{}' is not valid: {}", group, source),
+            TeleportError::RemoteParse {
+                group: None,
+                source,
+            } => write!(f, "Remote group list is not valid: {}", source),
+            TeleportError::Config(message) => write!(f, "Config error: {}", message),
+            TeleportError::RateLimited { reset_at } => {
+                let wait = reset_at.and_then(|t| t.duration_since(SystemTime::now()).ok());
+                match wait {
+                    Some(wait) => write!(
+                        f,
+                        "Rate limited by remote, try again in {} min",
+                        wait.as_secs().div_ceil(60).max(1)
+                    ),
+                    None => write!(f, "Rate limited by remote, try again later"),
+                }
+            }
         }
     }
 }
 
-impl std::error::Error for TeleportError {}
+/// An error and its causes, outermost first, as `error: cause: root cause`
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut chain = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        chain.push_str(": ");
+        chain.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    chain
+}
+
+impl std::error::Error for TeleportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TeleportError::IoError(e) => Some(e),
+            TeleportError::JsonError(e) | TeleportError::RemoteParse { source: e, .. } => Some(e),
+            TeleportError::Transport { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl From<io::Error> for TeleportError {
     fn from(error: io::Error) -> Self {
@@ -47,3 +146,22 @@ impl From<serde_json::Error> for TeleportError {
 }
 
 pub type Result<T> = std::result::Result<T, TeleportError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_errors_show_their_cause() {
+        let source = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let cause = error_chain(&source);
+        let error = TeleportError::Transport {
+            url: "not a url".to_string(),
+            source,
+        };
+        let message = error.to_string();
+        assert!(message.starts_with("Could not reach not a url, check your connection ("));
+        assert!(message.contains(&cause), "{}", message);
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
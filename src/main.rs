@@ -1,14 +1,19 @@
 use crossterm::{
-    event::{self, Event, KeyEventKind},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyEventKind, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use futures::StreamExt;
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io;
 use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::time::MissedTickBehavior;
 use tp_sync::TeleportError;
 use tp_sync::config::AppConfig;
-use tp_sync::ui::{App, AppEvent, handle_key_event, render};
+use tp_sync::ui::{App, AppEvent, TaskResult, handle_key_event, handle_mouse_event, render};
 
 #[tokio::main]
 async fn main() -> tp_sync::Result<()> {
@@ -33,19 +38,24 @@ async fn main() -> tp_sync::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app with config
-    let mut app = App::new(&config).await?;
+    let (task_sender, task_receiver) = mpsc::unbounded_channel();
+    let mut app = App::new(&config, task_sender)?;
 
     // Run app
-    let res = run_app(&mut terminal, &mut app).await;
+    let res = run_app(&mut terminal, &mut app, task_receiver).await;
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -80,20 +90,53 @@ fn setup_config() -> tp_sync::Result<AppConfig> {
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    mut tasks: UnboundedReceiver<TaskResult>,
 ) -> tp_sync::Result<()> {
+    let mut events = EventStream::new();
+    let mut spinner = tokio::time::interval(Duration::from_millis(100));
+    spinner.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut dirty = true;
+
     loop {
-        terminal.draw(|f| render(f, app)).map_err(|e| {
-            tp_sync::TeleportError::IoError(io::Error::other(format!("Terminal draw error: {}", e)))
-        })?;
+        // Only redraw after something changed
+        if dirty {
+            terminal.draw(|f| render(f, app)).map_err(|e| {
+                tp_sync::TeleportError::IoError(io::Error::other(format!(
+                    "Terminal draw error: {}",
+                    e
+                )))
+            })?;
+            dirty = false;
+        }
 
-        if event::poll(Duration::from_millis(100))?
-            && let Event::Key(key) = event::read()?
-        {
-            // Only process key press events, ignore key release
-            if key.kind == KeyEventKind::Press
-                && let Some(AppEvent::Quit) = handle_key_event(app, key).await?
-            {
-                return Ok(());
+        tokio::select! {
+            event = events.next() => match event {
+                // Only process key press events, ignore key release
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                    if let Some(AppEvent::Quit) = handle_key_event(app, key)? {
+                        return Ok(());
+                    }
+                    dirty = true;
+                }
+                // Plain pointer movement changes nothing on screen
+                Some(Ok(Event::Mouse(mouse))) if mouse.kind != MouseEventKind::Moved => {
+                    if let Some(AppEvent::Quit) = handle_mouse_event(app, mouse)? {
+                        return Ok(());
+                    }
+                    dirty = true;
+                }
+                Some(Ok(Event::Resize(_, _))) => dirty = true,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            },
+            Some(result) = tasks.recv() => {
+                app.handle_task_result(result);
+                dirty = true;
+            }
+            _ = spinner.tick(), if app.tasks.is_busy() => {
+                app.tick_spinner();
+                dirty = true;
             }
         }
     }
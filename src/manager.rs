@@ -1,10 +1,59 @@
-use crate::types::{TeleportConfig, TeleportGroup};
+use crate::duplicates::{DedupeMode, DedupeReport, DuplicateCluster, DuplicateFinder};
+use crate::maps::MapDatabase;
+use crate::types::{Teleport, TeleportConfig, TeleportGroup};
 use crate::{Result, TeleportError};
-use std::collections::hash_map::DefaultHasher;
 use std::fs;
-use std::hash::{Hash, Hasher};
 use std::path::Path;
 
+/// What to do when installing a group whose name is already taken
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConflictPolicy {
+    /// Overwrite the existing group in place, keeping fields only the local copy has
+    Replace,
+    /// Install under a new name, e.g. "Name (remote)"
+    Rename,
+    /// Append teleports the existing group doesn't have yet
+    Merge,
+    /// Leave the existing group untouched
+    #[default]
+    Skip,
+}
+
+impl ConflictPolicy {
+    /// All policies in the order they are offered to the user
+    pub const ALL: [ConflictPolicy; 4] = [
+        ConflictPolicy::Skip,
+        ConflictPolicy::Replace,
+        ConflictPolicy::Rename,
+        ConflictPolicy::Merge,
+    ];
+
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Replace => "Replace",
+            ConflictPolicy::Rename => "Rename",
+            ConflictPolicy::Merge => "Merge",
+            ConflictPolicy::Skip => "Skip",
+        }
+    }
+}
+
+/// Result of installing a group with a conflict policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallOutcome {
+    /// No conflict, the group was added
+    Added,
+    /// The existing group was overwritten
+    Replaced,
+    /// The group was added under the given name
+    Renamed(String),
+    /// This many teleports were appended to the existing group
+    Merged(usize),
+    /// The group already exists and was left untouched
+    Skipped,
+}
+
 /// Manager for handling teleport.json operations
 pub struct TeleportManager {
     config: TeleportConfig,
@@ -72,6 +121,64 @@ impl TeleportManager {
         Ok(())
     }
 
+    /// Add a teleport group, resolving a name conflict with the given policy
+    pub fn add_group_with_policy(
+        &mut self,
+        mut group: TeleportGroup,
+        policy: ConflictPolicy,
+    ) -> Result<InstallOutcome> {
+        if self.get_group(&group.name).is_none() {
+            self.config.teleport_groups.push(group);
+            return Ok(InstallOutcome::Added);
+        }
+
+        match policy {
+            ConflictPolicy::Replace => {
+                self.update_group(group)?;
+                Ok(InstallOutcome::Replaced)
+            }
+            ConflictPolicy::Rename => {
+                group.name = self.unique_name(&group.name, "remote");
+                let name = group.name.clone();
+                self.config.teleport_groups.push(group);
+                Ok(InstallOutcome::Renamed(name))
+            }
+            ConflictPolicy::Merge => {
+                let added = self.merge_teleports(&group.name, group.teleports)?;
+                Ok(InstallOutcome::Merged(added))
+            }
+            ConflictPolicy::Skip => Ok(InstallOutcome::Skipped),
+        }
+    }
+
+    /// Append teleports to an existing group, skipping exact copies it already has
+    ///
+    /// Returns the number of teleports added.
+    pub fn merge_teleports(&mut self, name: &str, teleports: Vec<Teleport>) -> Result<usize> {
+        let existing = self
+            .get_group_mut(name)
+            .ok_or_else(|| TeleportError::GroupNotFound(name.to_string()))?;
+        let mut added = 0;
+        for teleport in teleports {
+            if !existing.teleports.contains(&teleport) {
+                existing.teleports.push(teleport);
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Find a free group name like "Name (suffix)", "Name (suffix 2)", ...
+    pub fn unique_name(&self, name: &str, suffix: &str) -> String {
+        let mut candidate = format!("{} ({})", name, suffix);
+        let mut n = 2;
+        while self.get_group(&candidate).is_some() {
+            candidate = format!("{} ({} {})", name, suffix, n);
+            n += 1;
+        }
+        candidate
+    }
+
     /// Remove a teleport group by name
     pub fn remove_group(&mut self, name: &str) -> Result<TeleportGroup> {
         let index = self
@@ -84,20 +191,246 @@ impl TeleportManager {
         Ok(self.config.teleport_groups.remove(index))
     }
 
-    /// Get a hash of a teleport group by name
-    pub fn get_group_hash(&self, name: &str) -> Result<u64> {
-        let group = self
+    /// Move a teleport group to a new position, clamped to the end of the list
+    ///
+    /// Returns the index the group ended up at.
+    pub fn move_group_to(&mut self, name: &str, index: usize) -> Result<usize> {
+        let from = self.group_index(name)?;
+        let to = index.min(self.config.teleport_groups.len() - 1);
+        let group = self.config.teleport_groups.remove(from);
+        self.config.teleport_groups.insert(to, group);
+        Ok(to)
+    }
+
+    /// Move a teleport group one position up, returning false if it is already first
+    pub fn move_group_up(&mut self, name: &str) -> Result<bool> {
+        let index = self.group_index(name)?;
+        if index == 0 {
+            return Ok(false);
+        }
+        self.move_group_to(name, index - 1)?;
+        Ok(true)
+    }
+
+    /// Move a teleport group one position down, returning false if it is already last
+    pub fn move_group_down(&mut self, name: &str) -> Result<bool> {
+        let index = self.group_index(name)?;
+        if index + 1 >= self.config.teleport_groups.len() {
+            return Ok(false);
+        }
+        self.move_group_to(name, index + 1)?;
+        Ok(true)
+    }
+
+    /// Get the position of a teleport group by name
+    pub fn group_index(&self, name: &str) -> Result<usize> {
+        self.config
+            .teleport_groups
+            .iter()
+            .position(|g| g.name == name)
+            .ok_or_else(|| TeleportError::GroupNotFound(name.to_string()))
+    }
+
+    /// Replace an existing teleport group in place, keeping its position
+    pub fn replace_group(&mut self, group: TeleportGroup) -> Result<TeleportGroup> {
+        let existing = self
             .config
             .teleport_groups
+            .iter_mut()
+            .find(|g| g.name == group.name)
+            .ok_or_else(|| TeleportError::GroupNotFound(group.name.clone()))?;
+
+        Ok(std::mem::replace(existing, group))
+    }
+
+    /// Replace an existing teleport group with a newer version of it
+    ///
+    /// Group-level fields that only exist in the local copy are carried over.
+    pub fn update_group(&mut self, mut group: TeleportGroup) -> Result<TeleportGroup> {
+        let existing = self
+            .get_group(&group.name)
+            .ok_or_else(|| TeleportError::GroupNotFound(group.name.clone()))?;
+        for (key, value) in &existing.other_fields {
+            group
+                .other_fields
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self.replace_group(group)
+    }
+
+    /// Find duplicate and near-duplicate teleports across all groups
+    pub fn find_duplicates(&self, finder: &DuplicateFinder) -> Vec<DuplicateCluster> {
+        finder.find(&self.config.teleport_groups)
+    }
+
+    /// Remove or merge the duplicate copies of each cluster, keeping the first copy
+    pub fn dedupe(&mut self, clusters: &[DuplicateCluster], mode: DedupeMode) -> DedupeReport {
+        crate::duplicates::dedupe_groups(&mut self.config.teleport_groups, clusters, mode)
+    }
+
+    /// Merge several groups into one, removing the sources
+    ///
+    /// If `into` is one of the sources the others are merged into it, otherwise a new
+    /// group is created at the position of the first source. Duplicates inside the
+    /// merged group are folded together when a finder is given. At least two distinct
+    /// sources are needed, nothing changes otherwise.
+    pub fn merge_groups(
+        &mut self,
+        names: &[String],
+        into: &str,
+        finder: Option<&DuplicateFinder>,
+    ) -> Result<DedupeReport> {
+        let mut distinct: Vec<String> = Vec::with_capacity(names.len());
+        for name in names {
+            if !distinct.contains(name) {
+                distinct.push(name.clone());
+            }
+        }
+        let names = distinct.as_slice();
+        if names.len() < 2 {
+            return Err(invalid_input("Select at least two groups to merge"));
+        }
+        if let Some(missing) = names.iter().find(|n| self.get_group(n).is_none()) {
+            return Err(TeleportError::GroupNotFound(missing.clone()));
+        }
+        if !names.iter().any(|n| n == into) && self.get_group(into).is_some() {
+            return Err(TeleportError::GroupAlreadyExists(into.to_string()));
+        }
+
+        let position = names
             .iter()
-            .find(|g| g.name == name)
-            .ok_or_else(|| TeleportError::GroupNotFound(name.to_string()))?;
+            .filter_map(|n| self.group_index(n).ok())
+            .min()
+            .unwrap_or(self.config.teleport_groups.len());
+
+        let mut merged = match self.get_group(into) {
+            Some(group) => group.clone(),
+            None => TeleportGroup::new(into),
+        };
+        for name in names.iter().filter(|n| *n != into) {
+            let source = self.remove_group(name)?;
+            merged.teleports.extend(source.teleports);
+            for (key, value) in source.other_fields {
+                merged.other_fields.entry(key).or_insert(value);
+            }
+        }
 
-        let mut hasher = DefaultHasher::new();
-        // Hash the serialized JSON representation for consistency
-        let json = serde_json::to_string(group).unwrap();
-        json.hash(&mut hasher);
-        Ok(hasher.finish())
+        let report = match finder {
+            Some(finder) => {
+                let finder = finder.clone().within_groups_only();
+                let mut groups = [merged];
+                let clusters = finder.find(&groups);
+                let report =
+                    crate::duplicates::dedupe_groups(&mut groups, &clusters, DedupeMode::Merge);
+                let [group] = groups;
+                merged = group;
+                report
+            }
+            None => DedupeReport::default(),
+        };
+
+        if self.get_group(into).is_some() {
+            self.replace_group(merged)?;
+        } else {
+            let position = position.min(self.config.teleport_groups.len());
+            self.config.teleport_groups.insert(position, merged);
+        }
+        Ok(report)
+    }
+
+    /// Split a group into one group per map id, replacing the original
+    ///
+    /// New groups are named "Name - Map" and returned in order. A group with
+    /// teleports on fewer than two maps is left untouched.
+    pub fn split_group_by_map(&mut self, name: &str) -> Result<Vec<String>> {
+        let index = self.group_index(name)?;
+        let teleports = &self.config.teleport_groups[index].teleports;
+        if teleports.iter().all(|t| t.map == teleports[0].map) {
+            return Err(invalid_input(format!(
+                "'{}' has no teleports on more than one map to split",
+                name
+            )));
+        }
+        let group = self.config.teleport_groups.remove(index);
+
+        let mut by_map: Vec<(u32, Vec<Teleport>)> = Vec::new();
+        for teleport in group.teleports {
+            match by_map.iter_mut().find(|(map, _)| *map == teleport.map) {
+                Some((_, teleports)) => teleports.push(teleport),
+                None => by_map.push((teleport.map, vec![teleport])),
+            }
+        }
+
+        let maps = MapDatabase::bundled();
+        let mut names = Vec::new();
+        for (offset, (map, teleports)) in by_map.into_iter().enumerate() {
+            let base = match maps.name_of(map) {
+                Some(map_name) => format!("{} - {}", group.name, map_name),
+                None => format!("{} - Map {}", group.name, map),
+            };
+            let new_name = if self.get_group(&base).is_some() {
+                self.unique_name(&base, "split")
+            } else {
+                base
+            };
+            self.config.teleport_groups.insert(
+                index + offset,
+                TeleportGroup {
+                    name: new_name.clone(),
+                    teleports,
+                    other_fields: group.other_fields.clone(),
+                },
+            );
+            names.push(new_name);
+        }
+        Ok(names)
+    }
+
+    /// Move the teleports at the given indices into a new group placed after the original
+    ///
+    /// Returns the name of the new group. The selection must not be empty or
+    /// point past the group's teleports.
+    pub fn split_group(&mut self, name: &str, indices: &[usize]) -> Result<String> {
+        let index = self.group_index(name)?;
+        let count = self.config.teleport_groups[index].teleports.len();
+        if indices.is_empty() || indices.iter().any(|&i| i >= count) {
+            return Err(invalid_input(format!(
+                "Invalid selection of teleports to split off '{}'",
+                name
+            )));
+        }
+        let new_name = self.unique_name(name, "split");
+
+        let group = &mut self.config.teleport_groups[index];
+        let split = group.subset(indices);
+        let mut i = 0;
+        group.teleports.retain(|_| {
+            let keep = !indices.contains(&i);
+            i += 1;
+            keep
+        });
+
+        self.config.teleport_groups.insert(
+            index + 1,
+            TeleportGroup {
+                name: new_name.clone(),
+                ..split
+            },
+        );
+        Ok(new_name)
+    }
+
+    /// Copy a group under a new name, placed after the original
+    pub fn duplicate_group(&mut self, name: &str, new_name: &str) -> Result<()> {
+        let index = self.group_index(name)?;
+        if self.get_group(new_name).is_some() {
+            return Err(TeleportError::GroupAlreadyExists(new_name.to_string()));
+        }
+        let mut copy = self.config.teleport_groups[index].clone();
+        copy.name = new_name.to_string();
+        self.config.teleport_groups.insert(index + 1, copy);
+        Ok(())
     }
 
     /// Get all teleport groups
@@ -139,8 +472,231 @@ impl TeleportManager {
     }
 }
 
+/// Error for a request that can't be applied to the current groups
+fn invalid_input(message: impl Into<String>) -> TeleportError {
+    TeleportError::IoError(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        message.into(),
+    ))
+}
+
 impl Default for TeleportManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn unknown_fields_survive_update_and_save() {
+        let path = std::env::temp_dir().join(format!("tp_sync_fields_{}.json", std::process::id()));
+        let original = json!({
+            "teleport_groups": [{
+                "name": "Raids",
+                "color": "red",
+                "teleports": [{
+                    "name": "Spirit Vale",
+                    "coordinates": [1.0, 2.0, 3.0],
+                    "map": 1062,
+                    "tooltip": null,
+                    "icon": "wing1"
+                }]
+            }],
+            "version": 3
+        });
+        fs::write(&path, original.to_string()).unwrap();
+
+        let mut manager = TeleportManager::load(&path).unwrap();
+        let mut remote: TeleportGroup = serde_json::from_value(json!({
+            "name": "Raids",
+            "author": "remote",
+            "teleports": [{
+                "name": "Spirit Vale",
+                "coordinates": [1.0, 2.0, 4.0],
+                "map": 1062,
+                "tooltip": "Wing 1",
+                "icon": "wing1-new"
+            }]
+        }))
+        .unwrap();
+        remote.other_fields.insert("color".into(), json!("blue"));
+        let outcome = manager
+            .add_group_with_policy(remote, ConflictPolicy::Replace)
+            .unwrap();
+        assert_eq!(outcome, InstallOutcome::Replaced);
+        manager.save_to_original().unwrap();
+
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved["version"], 3);
+        let group = &saved["teleport_groups"][0];
+        // The remote value wins, fields only the local copy had are kept
+        assert_eq!(group["color"], "blue");
+        assert_eq!(group["author"], "remote");
+        assert_eq!(group["teleports"][0]["icon"], "wing1-new");
+        assert_eq!(group["teleports"][0]["coordinates"][2], 4.0);
+    }
+
+    #[test]
+    fn update_keeps_local_only_group_fields() {
+        let mut local = TeleportGroup::new("Raids");
+        local.other_fields.insert("color".into(), json!("red"));
+        let mut manager = TeleportManager::from_config(TeleportConfig {
+            teleport_groups: vec![local],
+            ..Default::default()
+        });
+
+        manager.update_group(TeleportGroup::new("Raids")).unwrap();
+        assert_eq!(
+            manager.get_group("Raids").unwrap().other_fields["color"],
+            "red"
+        );
+        assert!(matches!(
+            manager.update_group(TeleportGroup::new("Missing")),
+            Err(TeleportError::GroupNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn merges_several_groups_into_one_of_them() {
+        let group = |name: &str, teleports: &[(&str, [f32; 3])]| {
+            let mut group = TeleportGroup::new(name);
+            group.teleports = teleports
+                .iter()
+                .map(|(name, coordinates)| Teleport::new(*name, 1, *coordinates))
+                .collect();
+            group
+        };
+        let mut manager = TeleportManager::from_config(TeleportConfig {
+            teleport_groups: vec![
+                group("Other", &[]),
+                group("Wing 1", &[("Vale", [0.0; 3])]),
+                group("Wing 2", &[("Vale again", [0.5, 0.0, 0.0])]),
+                group("Wing 3", &[("Keep", [100.0, 0.0, 0.0])]),
+            ],
+            ..Default::default()
+        });
+
+        let names = ["Wing 2".to_string(), "Wing 1".into(), "Wing 3".into()];
+        let report = manager
+            .merge_groups(&names, "Wing 2", Some(&DuplicateFinder::new(1.0)))
+            .unwrap();
+        assert_eq!(report.removed, 1);
+        let groups: Vec<_> = manager
+            .get_all_groups()
+            .iter()
+            .map(|g| g.name.as_str())
+            .collect();
+        assert_eq!(groups, ["Other", "Wing 2"]);
+        let teleports: Vec<_> = manager
+            .get_group("Wing 2")
+            .unwrap()
+            .teleports
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(teleports, ["Vale again", "Keep"]);
+    }
+
+    fn manager(groups: &[(&str, &[u32])]) -> TeleportManager {
+        let groups = groups
+            .iter()
+            .map(|(name, maps)| {
+                let mut group = TeleportGroup::new(*name);
+                group.other_fields.insert("color".into(), json!("red"));
+                group.teleports = maps
+                    .iter()
+                    .enumerate()
+                    .map(|(i, map)| Teleport::new(format!("T{}", i), *map, [0.0; 3]))
+                    .collect();
+                group
+            })
+            .collect();
+        TeleportManager::from_config(TeleportConfig {
+            teleport_groups: groups,
+            ..Default::default()
+        })
+    }
+
+    fn names(manager: &TeleportManager) -> Vec<&str> {
+        manager
+            .get_all_groups()
+            .iter()
+            .map(|g| g.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn moves_past_either_end_do_nothing() {
+        let mut manager = manager(&[("A", &[]), ("B", &[]), ("C", &[])]);
+        assert!(!manager.move_group_up("A").unwrap());
+        assert!(!manager.move_group_down("C").unwrap());
+        assert_eq!(names(&manager), ["A", "B", "C"]);
+
+        assert!(manager.move_group_down("A").unwrap());
+        assert!(manager.move_group_up("C").unwrap());
+        assert_eq!(names(&manager), ["B", "C", "A"]);
+    }
+
+    #[test]
+    fn splitting_by_map_needs_two_maps() {
+        let mut manager = manager(&[
+            ("Empty", &[]),
+            ("Single", &[1062, 1062]),
+            ("Both", &[1062, 7, 1062]),
+        ]);
+        let before = manager.get_config().clone();
+        assert!(manager.split_group_by_map("Empty").is_err());
+        assert!(manager.split_group_by_map("Single").is_err());
+        assert_eq!(manager.get_config(), &before);
+
+        let split = manager.split_group_by_map("Both").unwrap();
+        assert_eq!(split.len(), 2);
+        assert_eq!(names(&manager)[..2], ["Empty", "Single"]);
+        assert_eq!(manager.get_group(&split[0]).unwrap().teleports.len(), 2);
+        assert_eq!(
+            manager.get_group(&split[1]).unwrap().other_fields["color"],
+            "red"
+        );
+    }
+
+    #[test]
+    fn splitting_needs_a_valid_selection() {
+        let mut manager = manager(&[("Raids", &[1, 1, 1])]);
+        assert!(manager.split_group("Raids", &[]).is_err());
+        assert!(manager.split_group("Raids", &[1, 3]).is_err());
+        assert_eq!(names(&manager), ["Raids"]);
+
+        assert_eq!(
+            manager.split_group("Raids", &[2, 0]).unwrap(),
+            "Raids (split)"
+        );
+        assert_eq!(manager.get_group("Raids").unwrap().teleports.len(), 1);
+        assert_eq!(
+            manager.get_group("Raids (split)").unwrap().teleports.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn merging_needs_two_distinct_groups() {
+        let mut manager = manager(&[("A", &[1]), ("B", &[2])]);
+        assert!(manager.merge_groups(&[], "New", None).is_err());
+        assert!(
+            manager
+                .merge_groups(&["A".into(), "A".into()], "New", None)
+                .is_err()
+        );
+        assert_eq!(names(&manager), ["A", "B"]);
+
+        manager
+            .merge_groups(&["A".into(), "B".into(), "A".into()], "New", None)
+            .unwrap();
+        assert_eq!(names(&manager), ["New"]);
+        assert_eq!(manager.get_group("New").unwrap().teleports.len(), 2);
+    }
+}
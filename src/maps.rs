@@ -0,0 +1,161 @@
+use crate::types::{Teleport, TeleportGroup};
+use crate::{Result, TeleportError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Map table shipped with the binary, a subset of the maps listed by the GW2 `/v2/maps` API
+///
+/// `map_rect` is `null` for maps whose bounds haven't been taken from the API yet.
+const BUNDLED_MAPS: &str = include_str!("../data/maps.json");
+
+/// Static information about a single GW2 map
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MapInfo {
+    pub id: u32,
+    pub name: String,
+    pub region: String,
+    pub continent: String,
+
+    /// Map bounds in map units: `[[x_min, y_min], [x_max, y_max]]`, if known
+    pub map_rect: Option<[[f32; 2]; 2]>,
+}
+
+/// Lookup table of known maps by id
+#[derive(Debug, Clone)]
+pub struct MapDatabase {
+    maps: Vec<MapInfo>,
+    index: HashMap<u32, usize>,
+}
+
+impl MapDatabase {
+    /// Get the map table bundled with the binary
+    pub fn bundled() -> &'static MapDatabase {
+        static BUNDLED: OnceLock<MapDatabase> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            MapDatabase::from_json(BUNDLED_MAPS).expect("bundled map table should be valid")
+        })
+    }
+
+    /// Parse a map table from a JSON array of map entries
+    pub fn from_json(json: &str) -> Result<Self> {
+        let maps: Vec<MapInfo> = serde_json::from_str(json)?;
+        Self::from_maps(maps)
+    }
+
+    /// Build a map table from a list of entries, rejecting duplicate ids
+    pub fn from_maps(maps: Vec<MapInfo>) -> Result<Self> {
+        let mut index = HashMap::with_capacity(maps.len());
+        for (i, map) in maps.iter().enumerate() {
+            if index.insert(map.id, i).is_some() {
+                return Err(TeleportError::IoError(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Duplicate map id in map table: {}", map.id),
+                )));
+            }
+        }
+        Ok(Self { maps, index })
+    }
+
+    /// Get a map by id
+    pub fn get(&self, id: u32) -> Option<&MapInfo> {
+        self.index.get(&id).map(|&i| &self.maps[i])
+    }
+
+    /// Check whether a map id is known
+    pub fn contains(&self, id: u32) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    /// Get the name of a map by id
+    pub fn name_of(&self, id: u32) -> Option<&str> {
+        self.get(id).map(|m| m.name.as_str())
+    }
+
+    /// Human readable label for a map id, e.g. "Queensdale (15)"
+    pub fn label(&self, id: u32) -> String {
+        match self.name_of(id) {
+            Some(name) => format!("{} ({})", name, id),
+            None => format!("Unknown map ({})", id),
+        }
+    }
+
+    /// Get all known maps in table order
+    pub fn maps(&self) -> &[MapInfo] {
+        &self.maps
+    }
+
+    /// Get the teleports of a group that reference map ids missing from the table
+    pub fn unknown_teleports<'a>(&self, group: &'a TeleportGroup) -> Vec<&'a Teleport> {
+        group
+            .teleports
+            .iter()
+            .filter(|t| !self.contains(t.map))
+            .collect()
+    }
+}
+
+/// Look up a map by id in the bundled map table
+pub fn lookup(id: u32) -> Option<&'static MapInfo> {
+    MapDatabase::bundled().get(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_table_looks_up_names() {
+        let maps = MapDatabase::bundled();
+        assert_eq!(maps.name_of(15), Some("Queensdale"));
+        assert_eq!(maps.label(15), "Queensdale (15)");
+        assert_eq!(maps.label(999_999), "Unknown map (999999)");
+        assert!(lookup(999_999).is_none());
+    }
+
+    #[test]
+    fn bundled_rects_are_not_shared_placeholders() {
+        let maps = MapDatabase::bundled();
+        let rects: Vec<_> = maps.maps().iter().filter_map(|m| m.map_rect).collect();
+        for rect in &rects {
+            let [[x_min, y_min], [x_max, y_max]] = *rect;
+            assert!(x_min < x_max && y_min < y_max);
+        }
+        // Real maps rarely share bounds, a rect used over and over is made up
+        for rect in &rects {
+            assert!(rects.iter().filter(|r| *r == rect).count() <= 2);
+        }
+    }
+
+    #[test]
+    fn rect_is_optional_and_ids_are_unique() {
+        let json = r#"[
+            {"id": 1, "name": "A", "region": "R", "continent": "C", "map_rect": null},
+            {"id": 2, "name": "B", "region": "R", "continent": "C", "map_rect": [[0, 0], [10, 10]]}
+        ]"#;
+        let maps = MapDatabase::from_json(json).unwrap();
+        assert_eq!(maps.get(1).unwrap().map_rect, None);
+        assert_eq!(
+            maps.get(2).unwrap().map_rect,
+            Some([[0.0, 0.0], [10.0, 10.0]])
+        );
+
+        let duplicate = r#"[
+            {"id": 1, "name": "A", "region": "R", "continent": "C", "map_rect": null},
+            {"id": 1, "name": "B", "region": "R", "continent": "C", "map_rect": null}
+        ]"#;
+        assert!(MapDatabase::from_json(duplicate).is_err());
+    }
+
+    #[test]
+    fn unknown_teleports_are_flagged() {
+        let mut group = TeleportGroup::new("Mixed");
+        group.teleports.push(Teleport::new("Known", 15, [0.0; 3]));
+        group
+            .teleports
+            .push(Teleport::new("Unknown", 999_999, [0.0; 3]));
+        let unknown = MapDatabase::bundled().unknown_teleports(&group);
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].name, "Unknown");
+    }
+}
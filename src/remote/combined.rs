@@ -0,0 +1,146 @@
+use crate::remote::RemoteProvider;
+use crate::types::TeleportGroup;
+use crate::{Result, TeleportError};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Offers the groups of several remote sources as one list
+///
+/// A name offered by more than one source is listed as "Name (label)" for the
+/// later sources, so every listed name leads back to one source.
+pub struct CombinedProvider {
+    sources: Vec<(String, Box<dyn RemoteProvider>)>,
+    /// Listed name to source index and the name that source knows it by
+    listed: Mutex<HashMap<String, (usize, String)>>,
+}
+
+impl CombinedProvider {
+    /// Combine sources, each with a short label used to tell clashing names apart
+    pub fn new(sources: Vec<(String, Box<dyn RemoteProvider>)>) -> Self {
+        Self {
+            sources,
+            listed: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Descriptions of the combined sources, in listing order
+    pub fn source_descriptions(&self) -> Vec<String> {
+        self.sources.iter().map(|(_, s)| s.describe()).collect()
+    }
+
+    fn lookup(&self, name: &str) -> Option<(usize, String)> {
+        let listed = self.listed.lock().unwrap_or_else(|e| e.into_inner());
+        listed.get(name).cloned()
+    }
+}
+
+#[async_trait::async_trait]
+impl RemoteProvider for CombinedProvider {
+    fn describe(&self) -> String {
+        self.source_descriptions().join(", ")
+    }
+
+    async fn list_available_groups(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut listed = HashMap::new();
+        for (index, (label, source)) in self.sources.iter().enumerate() {
+            for name in source.list_available_groups().await? {
+                let mut shown = name.clone();
+                let mut n = 1;
+                while listed.contains_key(&shown) {
+                    shown = match n {
+                        1 => format!("{} ({})", name, label),
+                        _ => format!("{} ({} {})", name, label, n),
+                    };
+                    n += 1;
+                }
+                listed.insert(shown.clone(), (index, name));
+                names.push(shown);
+            }
+        }
+        *self.listed.lock().unwrap_or_else(|e| e.into_inner()) = listed;
+        Ok(names)
+    }
+
+    async fn fetch_group(&self, name: &str) -> Result<TeleportGroup> {
+        if let Some((index, original)) = self.lookup(name) {
+            return self.sources[index].1.fetch_group(&original).await;
+        }
+        // Not listed yet, ask the sources in order
+        let mut last_error = None;
+        for (_, source) in &self.sources {
+            match source.fetch_group(name).await {
+                Ok(group) => return Ok(group),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| TeleportError::GroupNotFound(name.to_string())))
+    }
+
+    async fn fetch_groups(&self, names: &[String]) -> Result<Vec<TeleportGroup>> {
+        let mut groups = Vec::with_capacity(names.len());
+        for name in names {
+            groups.push(self.fetch_group(name).await?);
+        }
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Source offering empty groups whose only teleport names the source
+    struct Fixed(&'static str, Vec<&'static str>);
+
+    #[async_trait::async_trait]
+    impl RemoteProvider for Fixed {
+        fn describe(&self) -> String {
+            self.0.to_string()
+        }
+
+        async fn list_available_groups(&self) -> Result<Vec<String>> {
+            Ok(self.1.iter().map(|n| n.to_string()).collect())
+        }
+
+        async fn fetch_group(&self, name: &str) -> Result<TeleportGroup> {
+            if !self.1.contains(&name) {
+                return Err(TeleportError::GroupNotFound(name.to_string()));
+            }
+            let mut group = TeleportGroup::new(name);
+            group
+                .teleports
+                .push(crate::types::Teleport::new(self.0, 1, [0.0; 3]));
+            Ok(group)
+        }
+
+        async fn fetch_groups(&self, _names: &[String]) -> Result<Vec<TeleportGroup>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn clashing_names_stay_apart() {
+        let remote = CombinedProvider::new(vec![
+            (
+                "GitHub".into(),
+                Box::new(Fixed("github", vec!["Raids", "Maps"])),
+            ),
+            (
+                "pack".into(),
+                Box::new(Fixed("pack", vec!["Raids", "Markers"])),
+            ),
+        ]);
+        let names = remote.list_available_groups().await.unwrap();
+        assert_eq!(names, ["Raids", "Maps", "Raids (pack)", "Markers"]);
+
+        let source = |group: TeleportGroup| group.teleports[0].name.clone();
+        assert_eq!(source(remote.fetch_group("Raids").await.unwrap()), "github");
+        let clashing = remote.fetch_group("Raids (pack)").await.unwrap();
+        assert_eq!(clashing.name, "Raids");
+        assert_eq!(source(clashing), "pack");
+        assert_eq!(source(remote.fetch_group("Markers").await.unwrap()), "pack");
+        assert!(remote.fetch_group("Missing").await.is_err());
+        assert_eq!(remote.describe(), "github, pack");
+    }
+}
@@ -1,7 +1,14 @@
 use crate::remote::RemoteProvider;
 use crate::types::TeleportGroup;
 use crate::{Result, TeleportError};
+use reqwest::StatusCode;
 use serde::Deserialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Requests made for one URL before a transient failure is reported
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before the first retry, doubled for each further one
+const RETRY_DELAY: Duration = Duration::from_millis(500);
 
 pub struct GitHubProvider {
     repo_owner: String,
@@ -35,6 +42,91 @@ impl GitHubProvider {
             self.repo_owner, self.repo_name, self.branch, self.teleports_path, filename
         )
     }
+
+    /// GET a URL and return the body, retrying transient failures with a growing delay
+    ///
+    /// Rate limits are reported right away, retrying would only use up more requests.
+    async fn get_text(&self, url: &str) -> Result<String> {
+        let mut delay = RETRY_DELAY;
+        for _ in 1..MAX_ATTEMPTS {
+            match self.try_get_text(url).await {
+                Err(e) if e.is_transient() && !matches!(e, TeleportError::RateLimited { .. }) => {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+        self.try_get_text(url).await
+    }
+
+    /// GET a URL once and return the body, mapping failures to typed errors
+    async fn try_get_text(&self, url: &str) -> Result<String> {
+        let transport = |source| TeleportError::Transport {
+            url: url.to_string(),
+            source,
+        };
+
+        let response = self
+            .client
+            .get(url)
+            .header("User-Agent", "tp_sync")
+            .send()
+            .await
+            .map_err(transport)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            if let RateLimit::Exhausted { reset_at } = rate_limit(&response) {
+                return Err(TeleportError::RateLimited { reset_at });
+            }
+            return Err(TeleportError::HttpStatus {
+                status: status.as_u16(),
+                url: url.to_string(),
+            });
+        }
+
+        response.text().await.map_err(transport)
+    }
+}
+
+/// Whether a failed response was refused by the rate limit
+enum RateLimit {
+    /// Failed for another reason
+    NotLimited,
+    /// The limit is used up until `reset_at`, if GitHub said when
+    Exhausted { reset_at: Option<SystemTime> },
+}
+
+/// Check a failed response for an exhausted rate limit
+///
+/// GitHub answers 403 or 429 and sends either `retry-after` in seconds or
+/// `x-ratelimit-reset` as a unix timestamp.
+fn rate_limit(response: &reqwest::Response) -> RateLimit {
+    let status = response.status();
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return RateLimit::NotLimited;
+    }
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+
+    if let Some(secs) = header("retry-after") {
+        return RateLimit::Exhausted {
+            reset_at: Some(SystemTime::now() + Duration::from_secs(secs)),
+        };
+    }
+    if status == StatusCode::TOO_MANY_REQUESTS || header("x-ratelimit-remaining") == Some(0) {
+        return RateLimit::Exhausted {
+            reset_at: header("x-ratelimit-reset")
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+        };
+    }
+    RateLimit::NotLimited
 }
 
 #[derive(Deserialize)]
@@ -46,36 +138,21 @@ struct GitHubFile {
 
 #[async_trait::async_trait]
 impl RemoteProvider for GitHubProvider {
-    async fn list_available_groups(&self) -> Result<Vec<String>> {
-        let url = self.api_url();
+    fn describe(&self) -> String {
+        format!(
+            "GitHub {}/{} ({}/ on {})",
+            self.repo_owner, self.repo_name, self.teleports_path, self.branch
+        )
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "tp_sync")
-            .send()
-            .await
-            .map_err(|e| {
-                TeleportError::IoError(std::io::Error::other(format!(
-                    "Failed to fetch from GitHub: {}",
-                    e
-                )))
+    async fn list_available_groups(&self) -> Result<Vec<String>> {
+        let body = self.get_text(&self.api_url()).await?;
+        let files: Vec<GitHubFile> =
+            serde_json::from_str(&body).map_err(|source| TeleportError::RemoteParse {
+                group: None,
+                source,
             })?;
 
-        if !response.status().is_success() {
-            return Err(TeleportError::IoError(std::io::Error::other(format!(
-                "GitHub API returned status: {}",
-                response.status()
-            ))));
-        }
-
-        let files: Vec<GitHubFile> = response.json().await.map_err(|e| {
-            TeleportError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse GitHub response: {}", e),
-            ))
-        })?;
-
         // Filter for JSON files and extract group names
         let group_names: Vec<String> = files
             .into_iter()
@@ -93,30 +170,19 @@ impl RemoteProvider for GitHubProvider {
         let filename = format!("{}.json", name);
         let url = self.raw_file_url(&filename);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("User-Agent", "tp_sync")
-            .send()
-            .await
-            .map_err(|e| {
-                TeleportError::IoError(std::io::Error::other(format!(
-                    "Failed to fetch group from GitHub: {}",
-                    e
-                )))
-            })?;
-
-        if !response.status().is_success() {
-            return Err(TeleportError::GroupNotFound(name.to_string()));
-        }
+        let body = match self.get_text(&url).await {
+            Err(TeleportError::HttpStatus { status: 404, .. }) => {
+                return Err(TeleportError::GroupNotFound(name.to_string()));
+            }
+            result => result?,
+        };
 
         // GitHub files contain a single TeleportGroup directly, not a TeleportConfig
-        let group: TeleportGroup = response.json().await.map_err(|e| {
-            TeleportError::IoError(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Failed to parse group JSON: {}", e),
-            ))
-        })?;
+        let group: TeleportGroup =
+            serde_json::from_str(&body).map_err(|source| TeleportError::RemoteParse {
+                group: Some(name.to_string()),
+                source,
+            })?;
 
         Ok(group)
     }
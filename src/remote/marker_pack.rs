@@ -0,0 +1,81 @@
+use crate::interop::MarkerPack;
+use crate::remote::RemoteProvider;
+use crate::types::TeleportGroup;
+use crate::{Result, TeleportError};
+use std::path::{Path, PathBuf};
+
+/// Serves the categories of a local TacO / Blish HUD marker pack as teleport groups
+pub struct MarkerPackProvider {
+    path: PathBuf,
+    /// Dotted path of the only category to offer, with its subcategories as one group
+    category: Option<String>,
+}
+
+impl MarkerPackProvider {
+    /// Create a provider over a marker pack XML file or a directory of XML files
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            category: None,
+        }
+    }
+
+    /// Only offer one category of the pack, e.g. `tyria.waypoints`
+    pub fn with_category(mut self, category: Option<String>) -> Self {
+        self.category = category;
+        self
+    }
+
+    /// Read and convert the pack on the blocking thread pool, it may be large
+    async fn groups(&self) -> Result<Vec<TeleportGroup>> {
+        let path = self.path.clone();
+        let category = self.category.clone();
+        tokio::task::spawn_blocking(move || load_groups(&path, category.as_deref()))
+            .await
+            .map_err(|e| TeleportError::IoError(std::io::Error::other(e)))?
+    }
+}
+
+fn load_groups(path: &Path, category: Option<&str>) -> Result<Vec<TeleportGroup>> {
+    let pack = MarkerPack::load(path)?;
+    match category {
+        Some(category) => Ok(pack.category_group(category).into_iter().collect()),
+        None => Ok(pack.to_groups()),
+    }
+}
+
+#[async_trait::async_trait]
+impl RemoteProvider for MarkerPackProvider {
+    fn describe(&self) -> String {
+        match &self.category {
+            Some(category) => format!("Marker pack {} ({})", self.path.display(), category),
+            None => format!("Marker pack {}", self.path.display()),
+        }
+    }
+
+    async fn list_available_groups(&self) -> Result<Vec<String>> {
+        Ok(self.groups().await?.into_iter().map(|g| g.name).collect())
+    }
+
+    async fn fetch_group(&self, name: &str) -> Result<TeleportGroup> {
+        self.groups()
+            .await?
+            .into_iter()
+            .find(|g| g.name == name)
+            .ok_or_else(|| TeleportError::GroupNotFound(name.to_string()))
+    }
+
+    async fn fetch_groups(&self, names: &[String]) -> Result<Vec<TeleportGroup>> {
+        let groups = self.groups().await?;
+        names
+            .iter()
+            .map(|name| {
+                groups
+                    .iter()
+                    .find(|g| &g.name == name)
+                    .cloned()
+                    .ok_or_else(|| TeleportError::GroupNotFound(name.clone()))
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,9 @@
+mod combined;
 mod github;
+mod marker_pack;
 mod provider;
 
+pub use combined::CombinedProvider;
 pub use github::GitHubProvider;
+pub use marker_pack::MarkerPackProvider;
 pub use provider::RemoteProvider;
@@ -4,6 +4,11 @@ use crate::types::TeleportGroup;
 /// Trait for fetching teleport groups from a remote source
 #[async_trait::async_trait]
 pub trait RemoteProvider: Send + Sync {
+    /// Where the groups come from, for display
+    fn describe(&self) -> String {
+        "Remote source".to_string()
+    }
+
     /// List all available group names from the remote source
     async fn list_available_groups(&self) -> Result<Vec<String>>;
 
@@ -1,3 +1,3 @@
 mod state;
 
-pub use state::{GroupSyncState, SyncStateManager, SyncStatus};
+pub use state::{GroupSyncState, PickTarget, SyncStateManager, SyncStatus};
@@ -1,77 +1,309 @@
-use crate::Result;
-use crate::manager::TeleportManager;
+use crate::digest::GroupDigest;
+use crate::duplicates::{DedupeMode, DedupeReport, DuplicateCluster, DuplicateFinder};
+use crate::manager::{ConflictPolicy, InstallOutcome, TeleportManager};
 use crate::remote::RemoteProvider;
 use crate::types::TeleportGroup;
+use crate::validation::{CoordinateChecker, CoordinateWarning};
+use crate::{Result, TeleportError};
 use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SyncStatus {
     Installed, // Exists locally
     Available, // Exists remotely but not locally
+    Conflict,  // Exists remotely under the name of a local group
+}
+
+/// Where cherry-picked teleports are installed
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickTarget {
+    /// A new local group, renamed if the name is taken
+    NewGroup(String),
+    /// Appended to an existing local group
+    ExistingGroup(String),
 }
 
 #[derive(Debug, Clone)]
 pub struct GroupSyncState {
     pub name: String,
     pub status: SyncStatus,
+    /// Content digest of the local group, if installed
+    pub digest: Option<GroupDigest>,
 }
 
 pub struct SyncStateManager {
     local_manager: TeleportManager,
-    remote_provider: Box<dyn RemoteProvider>,
+    remote_provider: Arc<dyn RemoteProvider>,
 }
 
 impl SyncStateManager {
     pub fn new(local_manager: TeleportManager, remote_provider: Box<dyn RemoteProvider>) -> Self {
         Self {
             local_manager,
-            remote_provider,
+            remote_provider: Arc::from(remote_provider),
         }
     }
 
+    /// Shared handle to the remote provider, for fetching outside of the manager
+    pub fn remote_provider(&self) -> Arc<dyn RemoteProvider> {
+        self.remote_provider.clone()
+    }
+
     /// Get all local groups
     pub fn get_local_groups(&self) -> Vec<TeleportGroup> {
         self.local_manager.get_all_groups().to_vec()
     }
 
+    /// Get a single local group by name
+    pub fn get_local_group(&self, name: &str) -> Option<&TeleportGroup> {
+        self.local_manager.get_group(name)
+    }
+
     /// Compute sync states by comparing local and remote
     pub async fn compute_sync_states(&self) -> Result<Vec<GroupSyncState>> {
-        let local_groups = self.local_manager.get_all_groups();
-        let local_names: HashSet<String> = local_groups.iter().map(|g| g.name.clone()).collect();
-
         let remote_names = self.remote_provider.list_available_groups().await?;
+        Ok(self.sync_states(remote_names))
+    }
+
+    /// Sync states of the local groups and of already listed remote groups
+    pub fn sync_states(&self, remote_names: Vec<String>) -> Vec<GroupSyncState> {
+        let local_groups = self.local_manager.get_all_groups();
+        let local_names: HashSet<&str> = local_groups.iter().map(|g| g.name.as_str()).collect();
 
         let mut states = Vec::new();
 
         // Add installed groups
-        for name in &local_names {
+        for group in local_groups {
             states.push(GroupSyncState {
-                name: name.clone(),
+                name: group.name.clone(),
                 status: SyncStatus::Installed,
+                digest: Some(GroupDigest::of(group)),
             });
         }
 
-        // Add available (not installed) groups
+        // Add remote groups, installing them may collide with a local group
         for name in remote_names {
-            if !local_names.contains(&name) {
-                states.push(GroupSyncState {
-                    name,
-                    status: SyncStatus::Available,
-                });
-            }
+            let status = if local_names.contains(name.as_str()) {
+                SyncStatus::Conflict
+            } else {
+                SyncStatus::Available
+            };
+            states.push(GroupSyncState {
+                name,
+                status,
+                digest: None,
+            });
         }
 
-        Ok(states)
+        states
     }
 
     /// Sync (download and install) a single group
     pub async fn sync_group(&mut self, name: &str) -> Result<()> {
-        let group = self.remote_provider.fetch_group(name).await?;
+        let group = self.fetch_remote_group(name).await?;
+        self.install_group(group)
+    }
+
+    /// Download a remote group without installing it
+    pub async fn fetch_remote_group(&self, name: &str) -> Result<TeleportGroup> {
+        self.remote_provider.fetch_group(name).await
+    }
+
+    /// Install an already downloaded group and save
+    pub fn install_group(&mut self, group: TeleportGroup) -> Result<()> {
         self.local_manager.add_group(group)?;
         self.local_manager.save_to_original()?;
         Ok(())
     }
 
+    /// Install an already downloaded group, resolving name conflicts, and save
+    pub fn install_group_with_policy(
+        &mut self,
+        group: TeleportGroup,
+        policy: ConflictPolicy,
+    ) -> Result<InstallOutcome> {
+        let outcome = self.local_manager.add_group_with_policy(group, policy)?;
+        if outcome != InstallOutcome::Skipped {
+            self.local_manager.save_to_original()?;
+        }
+        Ok(outcome)
+    }
+
+    /// Install several downloaded groups with one conflict policy and save once
+    pub fn install_groups_with_policy(
+        &mut self,
+        groups: Vec<TeleportGroup>,
+        policy: ConflictPolicy,
+    ) -> Result<Vec<InstallOutcome>> {
+        let mut outcomes = Vec::with_capacity(groups.len());
+        for group in groups {
+            outcomes.push(self.local_manager.add_group_with_policy(group, policy)?);
+        }
+        if outcomes.iter().any(|o| *o != InstallOutcome::Skipped) {
+            self.local_manager.save_to_original()?;
+        }
+        Ok(outcomes)
+    }
+
+    /// Install only the selected teleports of a downloaded group and save
+    pub fn install_selection(
+        &mut self,
+        group: &TeleportGroup,
+        indices: &[usize],
+        target: PickTarget,
+    ) -> Result<InstallOutcome> {
+        let mut picked = group.subset(indices);
+        let outcome = match target {
+            PickTarget::NewGroup(name) => {
+                picked.name = name;
+                self.local_manager
+                    .add_group_with_policy(picked, ConflictPolicy::Rename)?
+            }
+            PickTarget::ExistingGroup(name) => InstallOutcome::Merged(
+                self.local_manager
+                    .merge_teleports(&name, picked.teleports)?,
+            ),
+        };
+        self.local_manager.save_to_original()?;
+        Ok(outcome)
+    }
+
+    /// Suspicious coordinates in a downloaded remote group, to check before installing
+    pub fn check_remote_group(&self, group: &TeleportGroup) -> Vec<CoordinateWarning> {
+        suspicious(CoordinateChecker::default().check_group(group))
+    }
+
+    /// Suspicious coordinates in every local group
+    pub fn check_local_config(&self) -> Vec<CoordinateWarning> {
+        suspicious(CoordinateChecker::default().check_config(self.local_manager.get_config()))
+    }
+
+    /// Overwrite a local group with a downloaded remote version and save
+    ///
+    /// The local name is kept, as are group-level fields that only exist locally.
+    pub fn update_local_group(&mut self, local_name: &str, mut group: TeleportGroup) -> Result<()> {
+        group.name = local_name.to_string();
+        self.local_manager.update_group(group)?;
+        self.local_manager.save_to_original()?;
+        Ok(())
+    }
+
+    /// Whether a downloaded remote group differs from a local group, None if there
+    /// is no such local group
+    ///
+    /// Only the teleports and the group fields the remote has are compared, so the
+    /// local name and fields kept by an update don't count as changes.
+    pub fn remote_changed(&self, local_name: &str, remote: &TeleportGroup) -> Option<bool> {
+        let local = self.local_manager.get_group(local_name)?;
+        Some(GroupDigest::of_content(local, remote) != GroupDigest::of_content(remote, remote))
+    }
+
+    /// Find duplicate teleports across the local groups
+    pub fn find_local_duplicates(&self, finder: &DuplicateFinder) -> Vec<DuplicateCluster> {
+        self.local_manager.find_duplicates(finder)
+    }
+
+    /// Deduplicate local teleports and save
+    pub fn dedupe_local_groups(
+        &mut self,
+        clusters: &[DuplicateCluster],
+        mode: DedupeMode,
+    ) -> Result<DedupeReport> {
+        let report = self.local_manager.dedupe(clusters, mode);
+        if report.removed > 0 {
+            self.local_manager.save_to_original()?;
+        }
+        Ok(report)
+    }
+
+    /// Overwrite a local group with an edited copy and save
+    pub fn save_local_group(&mut self, group: TeleportGroup) -> Result<()> {
+        self.local_manager.replace_group(group)?;
+        self.local_manager.save_to_original()?;
+        Ok(())
+    }
+
+    /// Move a local group one position up or down and save, returning whether it moved
+    pub fn move_local_group(&mut self, name: &str, up: bool) -> Result<bool> {
+        let moved = if up {
+            self.local_manager.move_group_up(name)?
+        } else {
+            self.local_manager.move_group_down(name)?
+        };
+        if moved {
+            self.local_manager.save_to_original()?;
+        }
+        Ok(moved)
+    }
+
+    /// Merge local groups into one, folding duplicates together, and save
+    pub fn merge_local_groups(
+        &mut self,
+        names: &[String],
+        into: &str,
+        finder: &DuplicateFinder,
+    ) -> Result<DedupeReport> {
+        let report = self.local_manager.merge_groups(names, into, Some(finder))?;
+        self.local_manager.save_to_original()?;
+        Ok(report)
+    }
+
+    /// Split a local group into one group per map and save
+    pub fn split_local_group_by_map(&mut self, name: &str) -> Result<Vec<String>> {
+        let names = self.local_manager.split_group_by_map(name)?;
+        self.local_manager.save_to_original()?;
+        Ok(names)
+    }
+
+    /// Move selected teleports of a local group into a new group and save
+    pub fn split_local_group(&mut self, name: &str, indices: &[usize]) -> Result<String> {
+        let new_name = self.local_manager.split_group(name, indices)?;
+        self.local_manager.save_to_original()?;
+        Ok(new_name)
+    }
+
+    /// Copy a local group under a free "Name (copy)" name and save
+    pub fn duplicate_local_group(&mut self, name: &str) -> Result<String> {
+        let new_name = self.local_manager.unique_name(name, "copy");
+        self.local_manager.duplicate_group(name, &new_name)?;
+        self.local_manager.save_to_original()?;
+        Ok(new_name)
+    }
+
+    /// Add imported groups, merging into local groups of the same name, and save
+    ///
+    /// Returns the number of teleports that were added.
+    pub fn import_local_groups(&mut self, groups: Vec<TeleportGroup>) -> Result<usize> {
+        let mut added = 0;
+        for group in groups {
+            let count = group.teleports.len();
+            added += match self
+                .local_manager
+                .add_group_with_policy(group, ConflictPolicy::Merge)?
+            {
+                InstallOutcome::Merged(n) => n,
+                _ => count,
+            };
+        }
+        self.local_manager.save_to_original()?;
+        Ok(added)
+    }
+
+    /// Remove several local groups and save once
+    ///
+    /// Nothing is removed if any of the groups doesn't exist.
+    pub fn remove_local_groups(&mut self, names: &[String]) -> Result<()> {
+        if let Some(missing) = names.iter().find(|n| self.get_local_group(n).is_none()) {
+            return Err(TeleportError::GroupNotFound(missing.clone()));
+        }
+        for name in names {
+            self.local_manager.remove_group(name)?;
+        }
+        self.local_manager.save_to_original()?;
+        Ok(())
+    }
+
     /// Remove a local group
     pub fn remove_local_group(&mut self, name: &str) -> Result<()> {
         self.local_manager.remove_group(name)?;
@@ -79,3 +311,111 @@ impl SyncStateManager {
         Ok(())
     }
 }
+
+/// Drop the warnings that only say a map's bounds are unknown
+fn suspicious(warnings: Vec<CoordinateWarning>) -> Vec<CoordinateWarning> {
+    warnings
+        .into_iter()
+        .filter(|w| w.issue.is_suspicious())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TeleportConfig;
+
+    struct NoRemote;
+
+    #[async_trait::async_trait]
+    impl RemoteProvider for NoRemote {
+        async fn list_available_groups(&self) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        async fn fetch_group(&self, name: &str) -> Result<TeleportGroup> {
+            Err(TeleportError::GroupNotFound(name.into()))
+        }
+
+        async fn fetch_groups(&self, _names: &[String]) -> Result<Vec<TeleportGroup>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn same_name_remote_groups_are_conflicts() {
+        let local = TeleportManager::from_config(TeleportConfig {
+            teleport_groups: vec![TeleportGroup::new("Raids")],
+            ..Default::default()
+        });
+        let manager = SyncStateManager::new(local, Box::new(NoRemote));
+        let states = manager.sync_states(vec!["Raids".into(), "Fractals".into()]);
+        let statuses: Vec<_> = states
+            .iter()
+            .map(|s| (s.name.as_str(), s.status.clone()))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("Raids", SyncStatus::Installed),
+                ("Raids", SyncStatus::Conflict),
+                ("Fractals", SyncStatus::Available),
+            ]
+        );
+    }
+
+    #[test]
+    fn installed_and_updated_groups_match_their_remote_version() {
+        let path = std::env::temp_dir().join(format!("tp_sync_state_{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"teleport_groups": [{"name": "Raids", "color": "red", "teleports": []}]}"#,
+        )
+        .unwrap();
+        let mut manager =
+            SyncStateManager::new(TeleportManager::load(&path).unwrap(), Box::new(NoRemote));
+
+        let mut remote = TeleportGroup::new("Raids");
+        remote.other_fields.insert("author".into(), "remote".into());
+        remote
+            .teleports
+            .push(crate::types::Teleport::new("Gate", 1062, [1.0, 2.0, 3.0]));
+        assert_eq!(manager.remote_changed("Raids", &remote), Some(true));
+
+        // The local-only color is kept by the install, the renamed copy keeps its name
+        manager
+            .install_group_with_policy(remote.clone(), ConflictPolicy::Replace)
+            .unwrap();
+        manager
+            .install_group_with_policy(remote.clone(), ConflictPolicy::Rename)
+            .unwrap();
+        assert_eq!(manager.remote_changed("Raids", &remote), Some(false));
+        assert_eq!(
+            manager.remote_changed("Raids (remote)", &remote),
+            Some(false)
+        );
+
+        remote.teleports[0].coordinates[2] = 4.0;
+        assert_eq!(manager.remote_changed("Raids", &remote), Some(true));
+        manager.update_local_group("Raids", remote.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(manager.remote_changed("Raids", &remote), Some(false));
+        assert_eq!(
+            manager.get_local_group("Raids").unwrap().other_fields["color"],
+            "red"
+        );
+        assert_eq!(manager.remote_changed("Missing", &remote), None);
+    }
+
+    #[test]
+    fn removing_several_groups_is_all_or_nothing() {
+        let local = TeleportManager::from_config(TeleportConfig {
+            teleport_groups: vec![TeleportGroup::new("A"), TeleportGroup::new("B")],
+            ..Default::default()
+        });
+        let mut manager = SyncStateManager::new(local, Box::new(NoRemote));
+        let result = manager.remove_local_groups(&["A".into(), "Missing".into()]);
+        assert!(matches!(result, Err(TeleportError::GroupNotFound(name)) if name == "Missing"));
+        assert_eq!(manager.get_local_groups().len(), 2);
+    }
+}
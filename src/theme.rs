@@ -0,0 +1,230 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Built-in colour scheme picked with `theme` in config.toml
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Terminal default colours only, same as setting `NO_COLOR`
+    NoColor,
+}
+
+impl ThemeName {
+    pub fn is_default(&self) -> bool {
+        *self == ThemeName::default()
+    }
+}
+
+/// Part of the interface a colour applies to, the keys of the `[colors]` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorRole {
+    Title,
+    ActiveBorder,
+    Selection,
+    Status,
+    Marked,
+    Matched,
+    Muted,
+    Error,
+    Accent,
+    Added,
+    Removed,
+    Modified,
+    PopupBackground,
+    Text,
+}
+
+/// A colour written as a name (`lightblue`), a hex code (`#ff8800`) or an ANSI index (`208`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorValue(pub Color);
+
+impl Serialize for ColorValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map(ColorValue)
+            .map_err(|_| serde::de::Error::custom(format!("unknown color '{}'", s)))
+    }
+}
+
+/// Colours the interface is drawn with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub title: Color,
+    pub active_border: Color,
+    /// Background of the selected row
+    pub selection: Color,
+    pub status: Color,
+    pub marked: Color,
+    /// Characters matching the search query
+    pub matched: Color,
+    /// Secondary text like map names and digests
+    pub muted: Color,
+    pub error: Color,
+    pub accent: Color,
+    pub added: Color,
+    pub removed: Color,
+    pub modified: Color,
+    pub popup_background: Color,
+    pub text: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            title: Color::Cyan,
+            active_border: Color::Green,
+            selection: Color::DarkGray,
+            status: Color::Yellow,
+            marked: Color::Magenta,
+            matched: Color::Yellow,
+            muted: Color::DarkGray,
+            error: Color::Red,
+            accent: Color::Cyan,
+            added: Color::Green,
+            removed: Color::Red,
+            modified: Color::Yellow,
+            popup_background: Color::Black,
+            text: Color::White,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            title: Color::Blue,
+            active_border: Color::Blue,
+            selection: Color::Gray,
+            status: Color::Blue,
+            marked: Color::Magenta,
+            matched: Color::Red,
+            muted: Color::DarkGray,
+            error: Color::Red,
+            accent: Color::Blue,
+            added: Color::Green,
+            removed: Color::Red,
+            // Dark orange, yellow is unreadable on white
+            modified: Color::Indexed(130),
+            popup_background: Color::White,
+            text: Color::Black,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            title: Color::White,
+            active_border: Color::LightYellow,
+            selection: Color::Blue,
+            status: Color::White,
+            marked: Color::LightMagenta,
+            matched: Color::LightYellow,
+            muted: Color::Gray,
+            error: Color::LightRed,
+            accent: Color::LightCyan,
+            added: Color::LightGreen,
+            removed: Color::LightRed,
+            modified: Color::LightYellow,
+            popup_background: Color::Black,
+            text: Color::White,
+        }
+    }
+
+    /// Terminal default colours everywhere, selection is shown reversed instead
+    pub fn no_color() -> Self {
+        Self {
+            title: Color::Reset,
+            active_border: Color::Reset,
+            selection: Color::Reset,
+            status: Color::Reset,
+            marked: Color::Reset,
+            matched: Color::Reset,
+            muted: Color::Reset,
+            error: Color::Reset,
+            accent: Color::Reset,
+            added: Color::Reset,
+            removed: Color::Reset,
+            modified: Color::Reset,
+            popup_background: Color::Reset,
+            text: Color::Reset,
+        }
+    }
+
+    pub fn named(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::HighContrast => Self::high_contrast(),
+            ThemeName::NoColor => Self::no_color(),
+        }
+    }
+
+    /// Build the theme from config, with `NO_COLOR` taking precedence over both
+    pub fn load(name: ThemeName, colors: &BTreeMap<ColorRole, ColorValue>) -> Self {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::no_color();
+        }
+        let mut theme = Self::named(name);
+        for (role, ColorValue(color)) in colors {
+            *theme.role_mut(*role) = *color;
+        }
+        theme
+    }
+
+    fn role_mut(&mut self, role: ColorRole) -> &mut Color {
+        match role {
+            ColorRole::Title => &mut self.title,
+            ColorRole::ActiveBorder => &mut self.active_border,
+            ColorRole::Selection => &mut self.selection,
+            ColorRole::Status => &mut self.status,
+            ColorRole::Marked => &mut self.marked,
+            ColorRole::Matched => &mut self.matched,
+            ColorRole::Muted => &mut self.muted,
+            ColorRole::Error => &mut self.error,
+            ColorRole::Accent => &mut self.accent,
+            ColorRole::Added => &mut self.added,
+            ColorRole::Removed => &mut self.removed,
+            ColorRole::Modified => &mut self.modified,
+            ColorRole::PopupBackground => &mut self.popup_background,
+            ColorRole::Text => &mut self.text,
+        }
+    }
+
+    /// Style of the selected row in a list
+    pub fn selected(&self) -> Style {
+        let style = Style::default()
+            .bg(self.selection)
+            .add_modifier(Modifier::BOLD);
+        if self.selection == Color::Reset {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        }
+    }
+
+    /// Border of the focused pane
+    pub fn focused_border(&self) -> Style {
+        let style = Style::default().fg(self.active_border);
+        if self.active_border == Color::Reset {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::digest::GroupDigest;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -8,6 +9,23 @@ pub struct Teleport {
     pub coordinates: [f32; 3],
     pub map: u32,
     pub tooltip: Option<String>,
+
+    /// Unknown per-teleport fields, preserved when saving
+    #[serde(flatten)]
+    pub other_fields: serde_json::Map<String, Value>,
+}
+
+impl Teleport {
+    /// Create a teleport with no tooltip and no extra fields
+    pub fn new(name: impl Into<String>, map: u32, coordinates: [f32; 3]) -> Self {
+        Self {
+            name: name.into(),
+            coordinates,
+            map,
+            tooltip: None,
+            other_fields: serde_json::Map::new(),
+        }
+    }
 }
 
 /// Represents a group of teleport locations
@@ -15,6 +33,40 @@ pub struct Teleport {
 pub struct TeleportGroup {
     pub name: String,
     pub teleports: Vec<Teleport>,
+
+    /// Unknown per-group fields, preserved when saving
+    #[serde(flatten)]
+    pub other_fields: serde_json::Map<String, Value>,
+}
+
+impl TeleportGroup {
+    /// Create an empty group with no extra fields
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            teleports: Vec::new(),
+            other_fields: serde_json::Map::new(),
+        }
+    }
+
+    /// Copy of this group with only the teleports at the given indices, in group order
+    pub fn subset(&self, indices: &[usize]) -> TeleportGroup {
+        TeleportGroup {
+            name: self.name.clone(),
+            teleports: self
+                .teleports
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| indices.contains(i))
+                .map(|(_, t)| t.clone())
+                .collect(),
+            other_fields: self.other_fields.clone(),
+        }
+    }
+    /// Stable content digest of this group
+    pub fn digest(&self) -> GroupDigest {
+        GroupDigest::of(self)
+    }
 }
 
 /// The teleport configuration structure - only parses teleport_groups
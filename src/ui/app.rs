@@ -1,9 +1,24 @@
 use crate::Result;
+use crate::activity::{ActivityLog, LogLevel};
 use crate::config::AppConfig;
-use crate::manager::TeleportManager;
-use crate::remote::GitHubProvider;
+use crate::diff::GroupDiff;
+use crate::duplicates::{DedupeMode, DuplicateCluster, DuplicateFinder};
+use crate::interop;
+use crate::keymap::Keymap;
+use crate::manager::{ConflictPolicy, InstallOutcome, TeleportManager};
+use crate::remote::{CombinedProvider, GitHubProvider, MarkerPackProvider, RemoteProvider};
 use crate::sync::{SyncStateManager, SyncStatus};
+use crate::theme::Theme;
+use crate::types::TeleportGroup;
+use crate::ui::list::{select_next, select_prev};
+use crate::ui::{FetchPurpose, GroupEditor, TaskResult, TaskRunner, TeleportPicker, filter_names};
+use crate::validation::CoordinateWarning;
+use ratatui::layout::{Margin, Position, Rect};
 use ratatui::widgets::ListState;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Instant;
+use tokio::sync::mpsc::UnboundedSender;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Pane {
@@ -14,8 +29,33 @@ pub enum Pane {
 #[derive(Debug, Clone)]
 pub enum AppMode {
     Normal,
-    ConfirmDelete(String),
-    ConfirmInstall(String),
+    ConfirmDelete(Vec<String>),
+    ConfirmInstall(Vec<String>),
+    ConfirmDedupe(Vec<DuplicateCluster>),
+    EditGroup,
+    ViewGroup(String),
+    PickTeleports,
+    ConfirmSplit(String),
+    ConfirmMerge(Vec<String>),
+    Search,
+    ViewDiff,
+}
+
+/// A local group compared with its remote version
+pub struct DiffView {
+    pub local_name: String,
+    pub remote_name: String,
+    pub diff: GroupDiff,
+}
+
+/// Screen areas from the last draw that react to mouse clicks
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub local_list: Rect,
+    pub remote_list: Rect,
+    pub log: Rect,
+    pub confirm_yes: Option<Rect>,
+    pub confirm_no: Option<Rect>,
 }
 
 pub struct App {
@@ -26,18 +66,87 @@ pub struct App {
     pub remote_list_state: ListState,
     pub local_groups: Vec<String>,
     pub remote_groups: Vec<String>,
+    /// Fuzzy filter applied to both panes, empty to show everything
+    pub search_query: String,
+    /// Indices into `local_groups` shown in the local pane, in display order
+    pub local_view: Vec<usize>,
+    /// Indices into `remote_groups` shown in the remote pane, in display order
+    pub remote_view: Vec<usize>,
+    /// Local groups marked for a batch delete
+    pub local_marked: HashSet<String>,
+    /// Remote groups marked for a batch install
+    pub remote_marked: HashSet<String>,
     pub status_message: Option<String>,
-    pub is_loading: bool,
+    /// Every status message and the details behind it
+    pub log: ActivityLog,
+    pub log_open: bool,
+    /// Log entries hidden below the log panel, 0 to follow the newest
+    pub log_scroll: usize,
+    /// Remote work running in the background
+    pub tasks: TaskRunner,
+    pub spinner_frame: usize,
+    /// All group names the remote offered when last listed
+    pub remote_names: Vec<String>,
+    pub duplicate_distance: f32,
+    pub install_policy: ConflictPolicy,
+    pub dedupe_mode: DedupeMode,
+    /// Suspicious teleports in the groups of the install dialog
+    pub install_warnings: Vec<CoordinateWarning>,
+    pub editor: Option<GroupEditor>,
+    /// Remote groups fetched since the remote was last listed, keyed by remote name
+    pub remote_cache: HashMap<String, TeleportGroup>,
+    /// Whether each group in `remote_cache` differs from the local group it collides with
+    pub remote_changes: HashMap<String, bool>,
+    /// Remote groups being downloaded in the background to check for updates
+    pub comparing: HashSet<String>,
+    pub details_scroll: u16,
+    pub picker: Option<TeleportPicker>,
+    pub diff: Option<DiffView>,
+    /// Index into `merge_targets()` chosen in the merge dialog
+    pub merge_target: usize,
+    pub export_dir: PathBuf,
+    /// Where the remote groups come from, one entry per source
+    pub remote_sources: Vec<String>,
+    pub teleport_json_path: PathBuf,
+    pub config_path: Option<PathBuf>,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub hit_areas: HitAreas,
+    /// Help overlay shown on top of the current mode
+    pub help_open: bool,
+    pub help_scroll: u16,
+    /// When, where and on which row the last list click landed
+    pub last_click: Option<(Instant, Pane, usize)>,
 }
 
 impl App {
-    pub async fn new(config: &AppConfig) -> Result<Self> {
+    /// Create the app, reporting background task results to `task_sender`
+    pub fn new(config: &AppConfig, task_sender: UnboundedSender<TaskResult>) -> Result<Self> {
         let local_manager = TeleportManager::load(&config.teleport_json_path)?;
-        let remote_provider = Box::new(GitHubProvider::new(
-            config.remote_repo_owner.clone(),
-            config.remote_repo_name.clone(),
-        ));
-        let sync_manager = SyncStateManager::new(local_manager, remote_provider);
+        let mut sources: Vec<(String, Box<dyn RemoteProvider>)> = vec![(
+            "GitHub".to_string(),
+            Box::new(GitHubProvider::new(
+                config.remote_repo_owner.clone(),
+                config.remote_repo_name.clone(),
+            )),
+        )];
+        if let Some(path) = &config.marker_pack_path {
+            sources.push((
+                "marker pack".to_string(),
+                Box::new(
+                    MarkerPackProvider::new(path.clone())
+                        .with_category(config.marker_pack_category.clone()),
+                ),
+            ));
+        }
+        let remote = CombinedProvider::new(sources);
+        let remote_sources = remote.source_descriptions();
+        let sync_manager = SyncStateManager::new(local_manager, Box::new(remote));
+        let tasks = TaskRunner::new(sync_manager.remote_provider(), task_sender);
+        let (log_file, log_error) = match config.log_file() {
+            Ok(path) => (Some(path), None),
+            Err(e) => (None, Some(e)),
+        };
 
         let mut app = Self {
             sync_manager,
@@ -47,59 +156,946 @@ impl App {
             remote_list_state: ListState::default(),
             local_groups: Vec::new(),
             remote_groups: Vec::new(),
+            search_query: String::new(),
+            local_view: Vec::new(),
+            remote_view: Vec::new(),
+            local_marked: HashSet::new(),
+            remote_marked: HashSet::new(),
             status_message: None,
-            is_loading: false,
+            log: ActivityLog::new(log_file),
+            log_open: false,
+            log_scroll: 0,
+            tasks,
+            spinner_frame: 0,
+            remote_names: Vec::new(),
+            duplicate_distance: config.duplicate_distance,
+            install_policy: ConflictPolicy::default(),
+            install_warnings: Vec::new(),
+            dedupe_mode: DedupeMode::default(),
+            editor: None,
+            remote_cache: HashMap::new(),
+            remote_changes: HashMap::new(),
+            comparing: HashSet::new(),
+            details_scroll: 0,
+            picker: None,
+            diff: None,
+            merge_target: 0,
+            export_dir: config.export_dir()?,
+            remote_sources,
+            teleport_json_path: config.teleport_json_path.clone(),
+            config_path: AppConfig::config_path().ok(),
+            keymap: config.keymap.clone(),
+            theme: config.theme(),
+            hit_areas: HitAreas::default(),
+            help_open: false,
+            help_scroll: 0,
+            last_click: None,
         };
 
-        app.refresh_data().await?;
+        app.refresh_data();
+        app.reload_remote();
 
-        if !app.local_groups.is_empty() {
+        if !app.local_view.is_empty() {
             app.local_list_state.select(Some(0));
         }
 
+        app.log.push(
+            LogLevel::Info,
+            format!(
+                "○ Loaded {} local groups from {}",
+                app.local_groups.len(),
+                config.teleport_json_path.display()
+            ),
+        );
+        if let Some(e) = log_error {
+            app.set_status(format!("⚠ Activity log is not written to a file: {}", e));
+        }
+        let warnings = app.sync_manager.check_local_config();
+        for warning in &warnings {
+            app.log.push(LogLevel::Warning, format!("⚠ {}", warning));
+        }
+        if !warnings.is_empty() {
+            app.set_status(format!(
+                "⚠ {} local teleports look suspicious",
+                warnings.len()
+            ));
+        }
+
         Ok(app)
     }
 
-    pub async fn refresh_data(&mut self) -> Result<()> {
-        self.is_loading = true;
+    /// Rebuild both panes from the local groups and the last remote listing
+    pub fn refresh_data(&mut self) {
+        let local_selected = self.pane_selection(&Pane::Local).cloned();
+        let remote_selected = self.pane_selection(&Pane::Remote).cloned();
+
+        self.local_groups.clear();
+        self.remote_groups.clear();
+        for state in self.sync_manager.sync_states(self.remote_names.clone()) {
+            match state.status {
+                SyncStatus::Installed => self.local_groups.push(state.name),
+                _ => self.remote_groups.push(state.name),
+            }
+        }
+        let fetched: Vec<String> = self.remote_cache.keys().cloned().collect();
+        for name in fetched {
+            self.check_remote_change(name);
+        }
+
+        self.local_marked
+            .retain(|name| self.local_groups.contains(name));
+        self.remote_marked
+            .retain(|name| self.remote_groups.contains(name));
+        self.apply_filter(local_selected, remote_selected);
+    }
+
+    /// List the remote groups again in the background
+    pub fn reload_remote(&mut self) {
+        self.tasks.list_remote();
+    }
 
-        let local = self.sync_manager.get_local_groups();
-        self.local_groups = local.iter().map(|g| g.name.clone()).collect();
+    /// Advance the busy spinner while background work is running
+    pub fn tick_spinner(&mut self) {
+        self.spinner_frame = self.spinner_frame.wrapping_add(1);
+    }
 
-        let states = self.sync_manager.compute_sync_states().await?;
-        self.remote_groups = states
-            .into_iter()
-            .filter(|s| matches!(s.status, SyncStatus::Available))
-            .map(|s| s.name)
-            .collect();
+    /// Apply the result of a background task
+    pub fn handle_task_result(&mut self, result: TaskResult) {
+        self.tasks.finish();
+        match result {
+            TaskResult::RemoteList(Ok(names)) => {
+                // Downloaded groups are kept for the session unless the remote dropped them
+                self.remote_cache.retain(|name, _| names.contains(name));
+                self.remote_changes.retain(|name, _| names.contains(name));
+                self.remote_names = names;
+                self.refresh_data();
 
-        self.is_loading = false;
-        Ok(())
+                let unchecked: Vec<String> = self
+                    .remote_names
+                    .iter()
+                    .filter(|name| {
+                        self.local_groups.contains(*name)
+                            && !self.remote_cache.contains_key(*name)
+                            && !self.comparing.contains(*name)
+                    })
+                    .cloned()
+                    .collect();
+                if !unchecked.is_empty() {
+                    self.comparing.extend(unchecked.iter().cloned());
+                    self.tasks.fetch_for_compare(unchecked);
+                }
+            }
+            // Keep the last known remote list if the remote can't be listed
+            TaskResult::RemoteList(Err(e)) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+            TaskResult::Fetched {
+                name,
+                purpose,
+                result,
+            } => match result {
+                Ok(group) => {
+                    self.cache_remote(name.clone(), group);
+                    // Only open the view if the user hasn't moved on
+                    let still_selected = matches!(self.active_pane, Pane::Remote)
+                        && self.selected_group() == Some(&name);
+                    if matches!(self.mode, AppMode::Normal) {
+                        match purpose {
+                            FetchPurpose::Details if still_selected => self.open_details(),
+                            FetchPurpose::Picker if still_selected => self.open_picker(),
+                            FetchPurpose::Diff => self.show_diff(&name),
+                            _ => {}
+                        }
+                    }
+                }
+                Err(e) => {
+                    self.set_status(format!("✗ Error: {}", e));
+                }
+            },
+            TaskResult::InstallFetched { names, fetched } => {
+                let mut failed = Vec::new();
+                for (name, result) in fetched {
+                    match result {
+                        Ok(group) => self.cache_remote(name, group),
+                        Err(e) => {
+                            self.log.push(
+                                LogLevel::Error,
+                                format!("✗ Failed to download '{}': {}", name, e),
+                            );
+                            failed.push((name, e));
+                        }
+                    }
+                }
+                match failed.as_slice() {
+                    [] => {}
+                    [(_, e)] if names.len() == 1 => self.set_status(format!("✗ Error: {}", e)),
+                    [(name, e), ..] => self.set_status(format!(
+                        "✗ {} of {} groups failed to download, '{}': {}",
+                        failed.len(),
+                        names.len(),
+                        name,
+                        e
+                    )),
+                }
+                let names: Vec<String> = names
+                    .into_iter()
+                    .filter(|name| self.remote_cache.contains_key(name))
+                    .collect();
+                if !names.is_empty() && matches!(self.mode, AppMode::Normal) {
+                    self.open_install_confirmation(names);
+                }
+            }
+            TaskResult::CsvRead(None) => {}
+            TaskResult::CsvRead(Some(import)) => self.apply_csv_import(import),
+            TaskResult::CompareFetched(fetched) => {
+                for (name, result) in fetched {
+                    self.comparing.remove(&name);
+                    // Skip groups the remote stopped listing while these downloaded
+                    if !self.remote_names.contains(&name) {
+                        continue;
+                    }
+                    match result {
+                        Ok(group) => self.cache_remote(name, group),
+                        Err(e) => self.log.push(
+                            LogLevel::Warning,
+                            format!("⚠ Could not check '{}' for updates: {}", name, e),
+                        ),
+                    }
+                }
+                let updates = self
+                    .local_groups
+                    .iter()
+                    .filter(|name| self.local_changed(name) == Some(true))
+                    .count();
+                if updates > 0 {
+                    self.log.push(
+                        LogLevel::Info,
+                        format!("○ Updates available for {} local groups", updates),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Keep a fetched remote group until the remote is listed again
+    fn cache_remote(&mut self, name: String, group: TeleportGroup) {
+        self.remote_cache.insert(name.clone(), group);
+        self.check_remote_change(name);
+    }
+
+    /// Compare a fetched remote group with the local group it collides with
+    fn check_remote_change(&mut self, remote_name: String) {
+        let changed = self.remote_cache.get(&remote_name).and_then(|remote| {
+            self.sync_manager
+                .remote_changed(self.local_conflict(&remote_name)?, remote)
+        });
+        match changed {
+            Some(changed) => self.remote_changes.insert(remote_name, changed),
+            None => self.remote_changes.remove(&remote_name),
+        };
+    }
+
+    /// Whether a fetched remote group differs from the local group it collides with,
+    /// None if it hasn't been fetched or has no local copy
+    pub fn remote_changed(&self, remote_name: &str) -> Option<bool> {
+        self.remote_changes.get(remote_name).copied()
+    }
+
+    /// Whether the remote version of a local group differs from it, None if unknown
+    pub fn local_changed(&self, local_name: &str) -> Option<bool> {
+        self.remote_changed(&self.remote_counterpart(local_name)?)
     }
 
     pub fn selected_group(&self) -> Option<&String> {
+        self.pane_selection(&self.active_pane)
+    }
+
+    /// Name of the group in the selected row of a pane
+    fn pane_selection(&self, pane: &Pane) -> Option<&String> {
+        let (state, view, groups) = match pane {
+            Pane::Local => (&self.local_list_state, &self.local_view, &self.local_groups),
+            Pane::Remote => (
+                &self.remote_list_state,
+                &self.remote_view,
+                &self.remote_groups,
+            ),
+        };
+        state
+            .selected()
+            .and_then(|row| view.get(row))
+            .and_then(|&i| groups.get(i))
+    }
+
+    /// Recompute the rows of both panes for the current search query
+    ///
+    /// The given groups stay selected if they are still shown, otherwise the
+    /// selection is clamped to the visible rows.
+    fn apply_filter(&mut self, local_selected: Option<String>, remote_selected: Option<String>) {
+        self.local_view = filter_names(&self.search_query, &self.local_groups);
+        self.remote_view = filter_names(&self.search_query, &self.remote_groups);
+        reselect(
+            &mut self.local_list_state,
+            &self.local_view,
+            &self.local_groups,
+            local_selected.as_deref(),
+        );
+        reselect(
+            &mut self.remote_list_state,
+            &self.remote_view,
+            &self.remote_groups,
+            remote_selected.as_deref(),
+        );
+    }
+
+    fn refilter(&mut self) {
+        let local_selected = self.pane_selection(&Pane::Local).cloned();
+        let remote_selected = self.pane_selection(&Pane::Remote).cloned();
+        self.apply_filter(local_selected, remote_selected);
+    }
+
+    fn marks(&self, pane: &Pane) -> (&HashSet<String>, &[usize], &[String]) {
+        match pane {
+            Pane::Local => (&self.local_marked, &self.local_view, &self.local_groups),
+            Pane::Remote => (&self.remote_marked, &self.remote_view, &self.remote_groups),
+        }
+    }
+
+    fn marks_mut(&mut self) -> &mut HashSet<String> {
+        match self.active_pane {
+            Pane::Local => &mut self.local_marked,
+            Pane::Remote => &mut self.remote_marked,
+        }
+    }
+
+    /// Whether a group in a pane is marked
+    pub fn is_marked(&self, pane: &Pane, name: &str) -> bool {
+        self.marks(pane).0.contains(name)
+    }
+
+    /// Marked groups of a pane, in list order
+    pub fn marked_groups(&self, pane: &Pane) -> Vec<String> {
+        let (marked, _, groups) = self.marks(pane);
+        groups
+            .iter()
+            .filter(|name| marked.contains(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Groups a batch action applies to: the marked ones, or else the selected one
+    fn action_targets(&self) -> Vec<String> {
+        let marked = self.marked_groups(&self.active_pane);
+        if marked.is_empty() {
+            self.selected_group().cloned().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    /// Mark or unmark the selected group and move to the next row
+    pub fn toggle_mark(&mut self) {
+        let Some(name) = self.selected_group().cloned() else {
+            return;
+        };
+        let marks = self.marks_mut();
+        if !marks.remove(&name) {
+            marks.insert(name);
+        }
+        self.navigate_down();
+    }
+
+    /// Mark every group shown in the active pane
+    pub fn mark_all(&mut self) {
+        let (_, view, groups) = self.marks(&self.active_pane);
+        let shown: Vec<String> = view.iter().map(|&i| groups[i].clone()).collect();
+        self.marks_mut().extend(shown);
+    }
+
+    /// Flip the marks of the groups shown in the active pane
+    pub fn invert_marks(&mut self) {
+        let (_, view, groups) = self.marks(&self.active_pane);
+        let shown: Vec<String> = view.iter().map(|&i| groups[i].clone()).collect();
+        let marks = self.marks_mut();
+        for name in shown {
+            if !marks.remove(&name) {
+                marks.insert(name);
+            }
+        }
+    }
+
+    pub fn start_search(&mut self) {
+        self.mode = AppMode::Search;
+    }
+
+    pub fn push_search_char(&mut self, c: char) {
+        self.search_query.push(c);
+        self.refilter();
+    }
+
+    pub fn pop_search_char(&mut self) {
+        self.search_query.pop();
+        self.refilter();
+    }
+
+    /// Leave search mode, keeping the filter applied
+    pub fn finish_search(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    /// Leave search mode and show all groups again
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.refilter();
+        self.mode = AppMode::Normal;
+    }
+
+    /// Get the contents of the selected group, if loaded
+    ///
+    /// Remote groups are only available once fetched into the cache.
+    pub fn selected_group_data(&self) -> Option<&TeleportGroup> {
+        let name = self.selected_group()?;
         match self.active_pane {
-            Pane::Local => self
-                .local_list_state
-                .selected()
-                .and_then(|i| self.local_groups.get(i)),
-            Pane::Remote => self
-                .remote_list_state
-                .selected()
-                .and_then(|i| self.remote_groups.get(i)),
+            Pane::Local => self.sync_manager.get_local_group(name),
+            Pane::Remote => self.remote_cache.get(name),
+        }
+    }
+
+    /// Open the detail popup, fetching remote groups in the background on first use
+    pub fn open_details(&mut self) {
+        let Some(group_name) = self.selected_group().cloned() else {
+            return;
+        };
+        if matches!(self.active_pane, Pane::Remote) && !self.remote_cache.contains_key(&group_name)
+        {
+            self.tasks.fetch(group_name, FetchPurpose::Details);
+            return;
+        }
+        self.details_scroll = 0;
+        self.mode = AppMode::ViewGroup(group_name);
+    }
+
+    /// Compare the selected group with its counterpart in the other pane
+    pub fn open_diff(&mut self) {
+        let Some(selected) = self.selected_group().cloned() else {
+            return;
+        };
+        let remote_name = match self.active_pane {
+            Pane::Remote => selected,
+            Pane::Local => match self.remote_counterpart(&selected) {
+                Some(name) => name,
+                None => {
+                    self.set_status(format!("○ No remote version of '{}' found", selected));
+                    return;
+                }
+            },
+        };
+        if self.remote_cache.contains_key(&remote_name) {
+            self.show_diff(&remote_name);
+        } else {
+            self.tasks.fetch(remote_name, FetchPurpose::Diff);
+        }
+    }
+
+    /// Local group a remote group collides with, by the name inside the file once fetched
+    pub fn local_conflict(&self, remote_name: &str) -> Option<&str> {
+        let name = self
+            .remote_cache
+            .get(remote_name)
+            .map_or(remote_name, |group| group.name.as_str());
+        self.sync_manager
+            .get_local_group(name)
+            .map(|group| group.name.as_str())
+    }
+
+    /// Remote name of a local group: a remote file of the same name, or an
+    /// already fetched remote group whose name matches
+    fn remote_counterpart(&self, local_name: &str) -> Option<String> {
+        if self.remote_names.iter().any(|name| name == local_name) {
+            return Some(local_name.to_string());
+        }
+        self.remote_cache
+            .iter()
+            .find(|(_, group)| group.name == local_name)
+            .map(|(name, _)| name.clone())
+    }
+
+    fn show_diff(&mut self, remote_name: &str) {
+        let Some(remote) = self.remote_cache.get(remote_name) else {
+            return;
+        };
+        let Some(local) = self
+            .sync_manager
+            .get_local_group(&remote.name)
+            .or_else(|| self.sync_manager.get_local_group(remote_name))
+        else {
+            self.set_status(format!("○ '{}' is not installed locally", remote.name));
+            return;
+        };
+
+        self.diff = Some(DiffView {
+            local_name: local.name.clone(),
+            remote_name: remote_name.to_string(),
+            diff: GroupDiff::between(local, remote),
+        });
+        self.details_scroll = 0;
+        self.mode = AppMode::ViewDiff;
+    }
+
+    /// Overwrite the compared local group with its remote version, keeping the local name
+    pub fn install_from_diff(&mut self) {
+        let Some(view) = self.diff.take() else {
+            return;
+        };
+        self.mode = AppMode::Normal;
+        let Some(remote) = self.remote_cache.get(&view.remote_name).cloned() else {
+            return;
+        };
+
+        let warnings = self.sync_manager.check_remote_group(&remote);
+        for warning in &warnings {
+            self.log.push(LogLevel::Warning, format!("⚠ {}", warning));
+        }
+        let result = self
+            .sync_manager
+            .update_local_group(&view.local_name, remote);
+        self.set_status(match result {
+            Ok(()) if warnings.is_empty() => {
+                format!("✓ Replaced '{}' with the remote version", view.local_name)
+            }
+            Ok(()) => format!(
+                "✓ Replaced '{}' with the remote version (⚠ {} teleports look suspicious)",
+                view.local_name,
+                warnings.len()
+            ),
+            Err(e) => format!("✗ Error: {}", e),
+        });
+        self.refresh_data();
+    }
+
+    pub fn close_diff(&mut self) {
+        self.diff = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Open the teleport picker for the selected remote group
+    pub fn open_picker(&mut self) {
+        if !matches!(self.active_pane, Pane::Remote) {
+            return;
         }
+        let Some(group_name) = self.selected_group().cloned() else {
+            return;
+        };
+        match self.remote_cache.get(&group_name) {
+            Some(group) => {
+                self.picker = Some(TeleportPicker::new(
+                    group_name,
+                    group.clone(),
+                    self.local_groups.clone(),
+                ));
+                self.mode = AppMode::PickTeleports;
+            }
+            None => self.tasks.fetch(group_name, FetchPurpose::Picker),
+        }
+    }
+
+    /// Install the picked teleports and close the picker
+    pub fn confirm_pick(&mut self) -> Result<()> {
+        let Some(picker) = self.picker.as_ref() else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
+        let indices = picker.picked_indices();
+        if indices.is_empty() {
+            self.set_status("○ No teleports selected".to_string());
+            return Ok(());
+        }
+        let Some(picker) = self.picker.take() else {
+            return Ok(());
+        };
+
+        if picker.split {
+            match self
+                .sync_manager
+                .split_local_group(&picker.group.name, &indices)
+            {
+                Ok(new_name) => {
+                    self.set_status(format!(
+                        "✓ Split {} teleports into '{}'",
+                        indices.len(),
+                        new_name
+                    ));
+                }
+                Err(e) => {
+                    self.set_status(format!("✗ Error: {}", e));
+                }
+            }
+            self.mode = AppMode::Normal;
+            self.refresh_data();
+            return Ok(());
+        }
+
+        match self
+            .sync_manager
+            .install_selection(&picker.group, &indices, picker.pick_target())
+        {
+            Ok(InstallOutcome::Merged(added)) => {
+                self.set_status(format!(
+                    "✓ Merged {} of {} picked teleports",
+                    added,
+                    indices.len()
+                ));
+            }
+            Ok(InstallOutcome::Renamed(name)) => {
+                self.set_status(format!(
+                    "✓ Installed {} teleports as '{}'",
+                    indices.len(),
+                    name
+                ));
+            }
+            Ok(_) => {
+                self.set_status(format!(
+                    "✓ Installed {} teleports as '{}'",
+                    indices.len(),
+                    picker.group.name
+                ));
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        self.mode = AppMode::Normal;
+        self.refresh_data();
+        Ok(())
+    }
+
+    /// Copy the selected local group under a new name
+    pub fn duplicate_selected_group(&mut self) -> Result<()> {
+        if !matches!(self.active_pane, Pane::Local) {
+            return Ok(());
+        }
+        let Some(group_name) = self.selected_group().cloned() else {
+            return Ok(());
+        };
+        match self.sync_manager.duplicate_local_group(&group_name) {
+            Ok(new_name) => {
+                self.set_status(format!("✓ Duplicated as '{}'", new_name));
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        self.refresh_data();
+        Ok(())
+    }
+
+    pub fn handle_split_by_map(&mut self) {
+        if matches!(self.active_pane, Pane::Local)
+            && let Some(group_name) = self.selected_group()
+        {
+            self.mode = AppMode::ConfirmSplit(group_name.clone());
+        }
+    }
+
+    pub fn confirm_split(&mut self, group_name: String) -> Result<()> {
+        match self.sync_manager.split_local_group_by_map(&group_name) {
+            Ok(names) => {
+                self.set_status(format!(
+                    "✓ Split '{}' into {} groups",
+                    group_name,
+                    names.len()
+                ));
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        self.mode = AppMode::Normal;
+        self.refresh_data();
+        Ok(())
+    }
+
+    /// Open the picker to split selected teleports off the selected local group
+    pub fn open_split_picker(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) {
+            return;
+        }
+        if let Some(group) = self.selected_group_data().cloned() {
+            self.picker = Some(TeleportPicker::for_split(group));
+            self.mode = AppMode::PickTeleports;
+        }
+    }
+
+    /// Ask where to merge the marked local groups, or the selected one
+    pub fn handle_merge(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) || self.local_groups.len() < 2 {
+            return;
+        }
+        let sources = self.action_targets();
+        if !sources.is_empty() {
+            self.mode = AppMode::ConfirmMerge(sources);
+            self.merge_target = 0;
+        }
+    }
+
+    /// Groups the sources can be merged into: one of the sources if there are
+    /// several, otherwise any other local group
+    pub fn merge_targets<'a>(&'a self, sources: &'a [String]) -> Vec<&'a String> {
+        if sources.len() > 1 {
+            sources.iter().collect()
+        } else {
+            self.local_groups
+                .iter()
+                .filter(|g| !sources.contains(g))
+                .collect()
+        }
+    }
+
+    pub fn cycle_merge_target(&mut self, forward: bool) {
+        let len = match &self.mode {
+            AppMode::ConfirmMerge(sources) => self.merge_targets(sources).len(),
+            _ => 0,
+        }
+        .max(1);
+        self.merge_target = if forward {
+            (self.merge_target + 1) % len
+        } else {
+            (self.merge_target + len - 1) % len
+        };
+    }
+
+    pub fn confirm_merge(&mut self, sources: Vec<String>) -> Result<()> {
+        let Some(target) = self
+            .merge_targets(&sources)
+            .get(self.merge_target)
+            .map(|t| t.to_string())
+        else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
+        let mut names = vec![target.clone()];
+        names.extend(sources.iter().filter(|name| **name != target).cloned());
+        let finder = DuplicateFinder::new(self.duplicate_distance);
+        match self
+            .sync_manager
+            .merge_local_groups(&names, &target, &finder)
+        {
+            Ok(report) => {
+                let merged = match &names[1..] {
+                    [name] => format!("'{}'", name),
+                    others => format!("{} groups", others.len()),
+                };
+                self.set_status(format!(
+                    "✓ Merged {} into '{}' ({} duplicates folded)",
+                    merged, target, report.removed
+                ));
+                self.local_marked.clear();
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        self.mode = AppMode::Normal;
+        self.refresh_data();
+        Ok(())
+    }
+
+    /// Local groups an export applies to: the marked ones, or else the selected one
+    fn export_targets(&self) -> Vec<TeleportGroup> {
+        let mut names = self.marked_groups(&Pane::Local);
+        if names.is_empty() {
+            names.extend(self.pane_selection(&Pane::Local).cloned());
+        }
+        names
+            .iter()
+            .filter_map(|name| self.sync_manager.get_local_group(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Write the marked local groups, or the selected one, as standalone group files
+    pub fn export_selected_group(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) {
+            return;
+        }
+        let groups = self.export_targets();
+        let mut written = Vec::new();
+        for group in &groups {
+            match interop::write_group_file(group, &self.export_dir) {
+                Ok(path) => written.push(path),
+                Err(e) => {
+                    self.set_status(format!("✗ Error exporting '{}': {}", group.name, e));
+                    return;
+                }
+            }
+        }
+        match written.as_slice() {
+            [] => {}
+            [path] => self.set_status(format!("✓ Exported to {}", path.display())),
+            _ => self.set_status(format!(
+                "✓ Exported {} groups to {}",
+                written.len(),
+                self.export_dir.display()
+            )),
+        }
+    }
+
+    /// Write the marked local groups, or the selected one, as one TacO / Blish HUD marker pack
+    pub fn export_marker_pack(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) {
+            return;
+        }
+        let groups = self.export_targets();
+        let path = match groups.as_slice() {
+            [] => return,
+            [group] => self
+                .export_dir
+                .join(interop::group_file_name(&group.name))
+                .with_extension("xml"),
+            _ => self.export_dir.join("markers.xml"),
+        };
+        let pack = interop::MarkerPack::from_groups(&groups);
+        self.set_status(match pack.write(&path) {
+            Ok(_) if groups.len() == 1 => format!("✓ Exported marker pack to {}", path.display()),
+            Ok(_) => format!(
+                "✓ Exported {} groups as a marker pack to {}",
+                groups.len(),
+                path.display()
+            ),
+            Err(e) => format!("✗ Error: {}", e),
+        });
+    }
+
+    /// Write all local groups into one spreadsheet with a row per teleport
+    pub fn export_csv(&mut self) {
+        let groups = self.sync_manager.get_local_groups();
+        let path = self.export_dir.join("teleports.csv");
+        self.set_status(match interop::write_csv(&groups, &path) {
+            Ok(rows) => format!("✓ Exported {} teleports to {}", rows, path.display()),
+            Err(e) => format!("✗ Error: {}", e),
+        });
+    }
+
+    /// Pick a CSV or TSV file in the background, its rows are imported once read
+    pub fn import_csv(&mut self) {
+        self.tasks.import_csv(self.export_dir.clone());
+    }
+
+    /// Import the rows of a picked spreadsheet into new or existing local groups
+    fn apply_csv_import(&mut self, import: Result<interop::CsvImport>) {
+        let result = import.and_then(|import| {
+            let groups = import.groups.len();
+            let errors = import.errors;
+            self.sync_manager
+                .import_local_groups(import.groups)
+                .map(|added| (added, groups, errors))
+        });
+        if let Ok((_, _, errors)) = &result {
+            for error in errors {
+                self.log
+                    .push(LogLevel::Warning, format!("⚠ Skipped {}", error));
+            }
+        }
+        self.set_status(match result {
+            Ok((added, groups, errors)) if errors.is_empty() => {
+                format!("✓ Imported {} teleports into {} groups", added, groups)
+            }
+            Ok((added, groups, errors)) => format!(
+                "⚠ Imported {} teleports into {} groups, skipped {} rows ({})",
+                added,
+                groups,
+                errors.len(),
+                errors[0]
+            ),
+            Err(e) => format!("✗ Error: {}", e),
+        });
+        self.refresh_data();
+    }
+
+    /// Write the marked local groups, or the selected one, into one bundle file
+    pub fn export_bundle(&mut self) {
+        let groups = self.export_targets();
+        if groups.is_empty() {
+            return;
+        }
+        let path = self.export_dir.join("bundle.json");
+        self.set_status(match interop::write_bundle(&groups, &path) {
+            Ok(_) => format!("✓ Exported {} groups to {}", groups.len(), path.display()),
+            Err(e) => format!("✗ Error: {}", e),
+        });
+    }
+
+    pub fn cancel_picker(&mut self) {
+        self.picker = None;
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn scroll_details(&mut self, up: bool) {
+        self.details_scroll = if up {
+            self.details_scroll.saturating_sub(1)
+        } else {
+            self.details_scroll.saturating_add(1)
+        };
+    }
+
+    /// Show a message in the status bar and record it in the activity log
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        self.log.status(message.clone());
+        self.status_message = Some(message);
+    }
+
+    pub fn toggle_log(&mut self) {
+        self.log_open = !self.log_open;
+        self.log_scroll = 0;
+    }
+
+    /// Scroll the open activity log by some entries, older when going up
+    pub fn scroll_log(&mut self, up: bool, lines: usize) {
+        if !self.log_open {
+            return;
+        }
+        let shown = usize::from(self.hit_areas.log.height.saturating_sub(2));
+        let max = self.log.entries().count().saturating_sub(shown);
+        self.log_scroll = if up {
+            (self.log_scroll + lines).min(max)
+        } else {
+            self.log_scroll.saturating_sub(lines)
+        };
+    }
+
+    /// Scroll the open activity log by a panel's worth of entries
+    pub fn page_log(&mut self, up: bool) {
+        let page = usize::from(self.hit_areas.log.height.saturating_sub(3)).max(1);
+        self.scroll_log(up, page);
+    }
+
+    pub fn open_help(&mut self) {
+        self.help_open = true;
+        self.help_scroll = 0;
+    }
+
+    pub fn close_help(&mut self) {
+        self.help_open = false;
+    }
+
+    pub fn scroll_help(&mut self, up: bool) {
+        self.help_scroll = if up {
+            self.help_scroll.saturating_sub(1)
+        } else {
+            self.help_scroll.saturating_add(1)
+        };
     }
 
     pub fn switch_pane(&mut self) {
         self.active_pane = match self.active_pane {
             Pane::Local => {
-                if self.remote_list_state.selected().is_none() && !self.remote_groups.is_empty() {
+                if self.remote_list_state.selected().is_none() && !self.remote_view.is_empty() {
                     self.remote_list_state.select(Some(0));
                 }
                 Pane::Remote
             }
             Pane::Remote => {
-                if self.local_list_state.selected().is_none() && !self.local_groups.is_empty() {
+                if self.local_list_state.selected().is_none() && !self.local_view.is_empty() {
                     self.local_list_state.select(Some(0));
                 }
                 Pane::Local
@@ -108,97 +1104,371 @@ impl App {
     }
 
     pub fn navigate_up(&mut self) {
-        let (state, items_len) = match self.active_pane {
-            Pane::Local => (&mut self.local_list_state, self.local_groups.len()),
-            Pane::Remote => (&mut self.remote_list_state, self.remote_groups.len()),
+        let (state, items_len) = self.active_list();
+        select_prev(state, items_len);
+    }
+
+    pub fn navigate_down(&mut self) {
+        let (state, items_len) = self.active_list();
+        select_next(state, items_len);
+    }
+
+    fn active_list(&mut self) -> (&mut ListState, usize) {
+        match self.active_pane {
+            Pane::Local => (&mut self.local_list_state, self.local_view.len()),
+            Pane::Remote => (&mut self.remote_list_state, self.remote_view.len()),
+        }
+    }
+
+    /// Pane whose list covers a screen position
+    pub fn pane_at(&self, position: Position) -> Option<Pane> {
+        if self.hit_areas.local_list.contains(position) {
+            Some(Pane::Local)
+        } else if self.hit_areas.remote_list.contains(position) {
+            Some(Pane::Remote)
+        } else {
+            None
+        }
+    }
+
+    /// Row of a pane's list shown at a screen position
+    pub fn row_at(&self, pane: &Pane, position: Position) -> Option<usize> {
+        let (area, state, items_len) = match pane {
+            Pane::Local => (
+                self.hit_areas.local_list,
+                &self.local_list_state,
+                self.local_view.len(),
+            ),
+            Pane::Remote => (
+                self.hit_areas.remote_list,
+                &self.remote_list_state,
+                self.remote_view.len(),
+            ),
         };
+        let inner = area.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return None;
+        }
+        let row = state.offset() + usize::from(position.y - inner.y);
+        (row < items_len).then_some(row)
+    }
 
-        if items_len == 0 {
-            return;
+    /// Focus a pane and select one of its rows
+    pub fn select_row(&mut self, pane: Pane, row: usize) {
+        match pane {
+            Pane::Local => self.local_list_state.select(Some(row)),
+            Pane::Remote => self.remote_list_state.select(Some(row)),
         }
+        self.active_pane = pane;
+    }
 
-        let i = match state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    items_len - 1
-                } else {
-                    i - 1
-                }
-            }
-            None => 0,
+    /// Focus a pane and move its selection one row without wrapping around
+    pub fn scroll_pane(&mut self, pane: Pane, up: bool) {
+        let (state, items_len) = match pane {
+            Pane::Local => (&mut self.local_list_state, self.local_view.len()),
+            Pane::Remote => (&mut self.remote_list_state, self.remote_view.len()),
         };
-        state.select(Some(i));
+        if items_len > 0 {
+            let i = match (state.selected(), up) {
+                (Some(i), true) => i.saturating_sub(1),
+                (Some(i), false) => (i + 1).min(items_len - 1),
+                (None, _) => 0,
+            };
+            state.select(Some(i));
+        }
+        self.active_pane = pane;
     }
 
-    pub fn navigate_down(&mut self) {
-        let (state, items_len) = match self.active_pane {
-            Pane::Local => (&mut self.local_list_state, self.local_groups.len()),
-            Pane::Remote => (&mut self.remote_list_state, self.remote_groups.len()),
+    /// Move the selected local group up or down and persist the new order
+    pub fn move_selected_group(&mut self, up: bool) -> Result<()> {
+        if !matches!(self.active_pane, Pane::Local) {
+            return Ok(());
+        }
+        if !self.search_query.is_empty() {
+            self.set_status("○ Clear the search to reorder groups".to_string());
+            return Ok(());
+        }
+        let Some(index) = self.local_list_state.selected() else {
+            return Ok(());
+        };
+        let Some(group_name) = self.local_groups.get(index).cloned() else {
+            return Ok(());
         };
 
-        if items_len == 0 {
+        match self.sync_manager.move_local_group(&group_name, up) {
+            Ok(true) => {
+                self.refresh_data();
+                let new_index = if up { index - 1 } else { index + 1 };
+                self.local_list_state.select(Some(new_index));
+            }
+            // Already at the top or bottom
+            Ok(false) => {}
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the teleport editor for the selected local group
+    pub fn open_editor(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) {
             return;
         }
+        let Some(group) = self
+            .selected_group()
+            .and_then(|name| self.sync_manager.get_local_group(name))
+            .cloned()
+        else {
+            return;
+        };
+        self.editor = Some(GroupEditor::new(group));
+        self.mode = AppMode::EditGroup;
+    }
 
-        let i = match state.selected() {
-            Some(i) => {
-                if i >= items_len - 1 {
-                    0
-                } else {
-                    i + 1
+    /// Save the edited group through the manager and close the editor
+    pub fn save_editor(&mut self) -> Result<()> {
+        let Some(editor) = self.editor.take() else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
+        let group_name = editor.group.name.clone();
+        if editor.dirty {
+            match self.sync_manager.save_local_group(editor.group) {
+                Ok(_) => {
+                    self.set_status(format!("✓ Saved '{}'", group_name));
+                }
+                Err(e) => {
+                    self.set_status(format!("✗ Error: {}", e));
                 }
             }
-            None => 0,
-        };
-        state.select(Some(i));
+        }
+        self.mode = AppMode::Normal;
+        self.refresh_data();
+        Ok(())
+    }
+
+    /// Close the editor and discard all changes
+    pub fn cancel_editor(&mut self) {
+        if self.editor.take().is_some_and(|e| e.dirty) {
+            self.set_status("○ Discarded changes".to_string());
+        }
+        self.mode = AppMode::Normal;
     }
 
     pub fn handle_delete(&mut self) {
-        if matches!(self.active_pane, Pane::Local)
-            && let Some(group_name) = self.selected_group()
-        {
-            self.mode = AppMode::ConfirmDelete(group_name.clone());
+        if !matches!(self.active_pane, Pane::Local) {
+            return;
+        }
+        let names = self.action_targets();
+        if !names.is_empty() {
+            self.mode = AppMode::ConfirmDelete(names);
         }
     }
 
     pub fn handle_space(&mut self) {
-        if matches!(self.active_pane, Pane::Remote)
-            && let Some(group_name) = self.selected_group()
-        {
-            self.mode = AppMode::ConfirmInstall(group_name.clone());
+        if !matches!(self.active_pane, Pane::Remote) {
+            return;
+        }
+        let names = self.action_targets();
+        if !names.is_empty() {
+            // Merging never loses local teleports, the dialog offers the rest
+            self.request_install(names, ConflictPolicy::Merge);
+        }
+    }
+
+    /// Ask to install remote groups once they are downloaded and checked
+    fn request_install(&mut self, names: Vec<String>, policy: ConflictPolicy) {
+        self.install_policy = policy;
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| !self.remote_cache.contains_key(*name))
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            self.open_install_confirmation(names);
+        } else {
+            self.tasks.fetch_for_install(names, missing);
+        }
+    }
+
+    /// Check the coordinates of cached remote groups and ask to install them
+    fn open_install_confirmation(&mut self, names: Vec<String>) {
+        self.install_warnings = names
+            .iter()
+            .filter_map(|name| self.remote_cache.get(name))
+            .flat_map(|group| self.sync_manager.check_remote_group(group))
+            .collect();
+        self.mode = AppMode::ConfirmInstall(names);
+    }
+
+    /// Cycle the conflict policy offered in the install dialog
+    pub fn cycle_install_policy(&mut self, forward: bool) {
+        let all = ConflictPolicy::ALL;
+        let i = all
+            .iter()
+            .position(|p| *p == self.install_policy)
+            .unwrap_or(0);
+        let next = if forward {
+            (i + 1) % all.len()
+        } else {
+            (i + all.len() - 1) % all.len()
+        };
+        self.install_policy = all[next];
+    }
+
+    pub fn handle_dedupe(&mut self) {
+        if !matches!(self.active_pane, Pane::Local) {
+            return;
+        }
+        let finder = DuplicateFinder::new(self.duplicate_distance);
+        let clusters = self.sync_manager.find_local_duplicates(&finder);
+        if clusters.is_empty() {
+            self.set_status("✓ No duplicate teleports found".to_string());
+        } else {
+            self.mode = AppMode::ConfirmDedupe(clusters);
+            self.dedupe_mode = DedupeMode::default();
         }
     }
 
-    pub async fn confirm_delete(&mut self, group_name: String) -> Result<()> {
-        match self.sync_manager.remove_local_group(&group_name) {
+    /// Switch between removing and merging duplicates in the dedupe dialog
+    pub fn cycle_dedupe_mode(&mut self) {
+        let all = DedupeMode::ALL;
+        let i = all.iter().position(|m| *m == self.dedupe_mode).unwrap_or(0);
+        self.dedupe_mode = all[(i + 1) % all.len()];
+    }
+
+    pub fn confirm_delete(&mut self, names: Vec<String>) -> Result<()> {
+        match self.sync_manager.remove_local_groups(&names) {
             Ok(_) => {
-                self.status_message = Some(format!("✓ Deleted '{}'", group_name));
+                if names.len() > 1 {
+                    for name in &names {
+                        self.log.status(format!("✓ Deleted '{}'", name));
+                    }
+                }
+                self.set_status(match names.as_slice() {
+                    [name] => format!("✓ Deleted '{}'", name),
+                    _ => format!("✓ Deleted {} groups", names.len()),
+                });
+                self.local_marked.clear();
             }
             Err(e) => {
-                self.status_message = Some(format!("✗ Error: {}", e));
+                self.set_status(format!("✗ Error: {}", e));
             }
         }
         self.mode = AppMode::Normal;
-        self.refresh_data().await?;
+        self.refresh_data();
         Ok(())
     }
 
-    pub async fn confirm_install(&mut self, group_name: String) -> Result<()> {
-        self.is_loading = true;
-        match self.sync_manager.sync_group(&group_name).await {
-            Ok(_) => {
-                self.status_message = Some(format!("✓ Installed '{}'", group_name));
+    /// Install the given remote groups, already downloaded when the dialog opened
+    pub fn confirm_install(&mut self, names: Vec<String>) {
+        self.mode = AppMode::Normal;
+        let warnings = std::mem::take(&mut self.install_warnings);
+        let (installed, groups): (Vec<String>, Vec<TeleportGroup>) = names
+            .iter()
+            .filter_map(|name| {
+                self.remote_cache
+                    .get(name)
+                    .map(|group| (name.clone(), group.clone()))
+            })
+            .unzip();
+
+        match self
+            .sync_manager
+            .install_groups_with_policy(groups, self.install_policy)
+        {
+            Ok(outcomes) => {
+                if installed.len() > 1 {
+                    for (name, outcome) in installed.iter().zip(&outcomes) {
+                        self.log.status(install_message(name, outcome));
+                    }
+                }
+                for warning in &warnings {
+                    self.log.push(LogLevel::Warning, format!("⚠ {}", warning));
+                }
+                let mut message = match (installed.as_slice(), outcomes.as_slice()) {
+                    ([name], [outcome]) => install_message(name, outcome),
+                    _ => {
+                        let skipped = outcomes
+                            .iter()
+                            .filter(|o| **o == InstallOutcome::Skipped)
+                            .count();
+                        let mut message = format!(
+                            "✓ Installed {} of {} groups",
+                            outcomes.len() - skipped,
+                            names.len()
+                        );
+                        if skipped > 0 {
+                            message.push_str(&format!(", {} already installed", skipped));
+                        }
+                        message
+                    }
+                };
+                if !warnings.is_empty() {
+                    message.push_str(&format!(
+                        " (⚠ {} teleports look suspicious)",
+                        warnings.len()
+                    ));
+                }
+                self.set_status(message);
+                self.remote_marked.clear();
             }
             Err(e) => {
-                self.status_message = Some(format!("✗ Error: {}", e));
+                self.set_status(format!("✗ Error: {}", e));
+            }
+        }
+        self.refresh_data();
+    }
+
+    pub fn confirm_dedupe(&mut self, clusters: Vec<DuplicateCluster>) -> Result<()> {
+        match self
+            .sync_manager
+            .dedupe_local_groups(&clusters, self.dedupe_mode)
+        {
+            Ok(report) => {
+                let verb = match self.dedupe_mode {
+                    DedupeMode::Remove => "Removed",
+                    DedupeMode::Merge => "Merged",
+                };
+                self.set_status(format!("✓ {} {} duplicate teleports", verb, report.removed));
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Error: {}", e));
             }
         }
         self.mode = AppMode::Normal;
-        self.refresh_data().await?;
+        self.refresh_data();
         Ok(())
     }
 
     pub fn cancel_confirmation(&mut self) {
+        self.install_warnings.clear();
         self.mode = AppMode::Normal;
     }
 }
+
+/// Status message for a single installed group
+fn install_message(name: &str, outcome: &InstallOutcome) -> String {
+    match outcome {
+        InstallOutcome::Added => format!("✓ Installed '{}'", name),
+        InstallOutcome::Replaced => format!("✓ Replaced '{}'", name),
+        InstallOutcome::Renamed(new_name) => format!("✓ Installed as '{}'", new_name),
+        InstallOutcome::Merged(added) => {
+            format!("✓ Merged {} teleports from '{}'", added, name)
+        }
+        InstallOutcome::Skipped => format!("○ Skipped '{}', already installed", name),
+    }
+}
+
+/// Select the row showing `name`, or keep the current row clamped to the visible rows
+fn reselect(state: &mut ListState, view: &[usize], groups: &[String], name: Option<&str>) {
+    if view.is_empty() {
+        state.select(None);
+        return;
+    }
+    let row = name
+        .and_then(|name| view.iter().position(|&i| groups[i] == name))
+        .or_else(|| state.selected().map(|row| row.min(view.len() - 1)));
+    state.select(row);
+}
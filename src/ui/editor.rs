@@ -0,0 +1,214 @@
+use crate::types::{Teleport, TeleportGroup};
+use crate::ui::list::{select_next, select_prev};
+use ratatui::widgets::ListState;
+
+/// Labels of the editable teleport fields, in form order
+pub const FORM_FIELDS: [&str; 6] = ["Name", "Tooltip", "Map", "X", "Y", "Z"];
+
+/// Working copy of a local group while its teleports are being edited
+pub struct GroupEditor {
+    pub group: TeleportGroup,
+    pub list_state: ListState,
+    pub form: Option<TeleportForm>,
+    pub dirty: bool,
+}
+
+/// Text inputs for adding or editing a single teleport
+pub struct TeleportForm {
+    /// Index of the teleport being edited, None when adding
+    pub index: Option<usize>,
+    pub values: [String; 6],
+    pub focused: usize,
+    pub error: Option<String>,
+    base: Teleport,
+}
+
+impl GroupEditor {
+    pub fn new(group: TeleportGroup) -> Self {
+        let mut list_state = ListState::default();
+        if !group.teleports.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            group,
+            list_state,
+            form: None,
+            dirty: false,
+        }
+    }
+
+    pub fn navigate_up(&mut self) {
+        select_prev(&mut self.list_state, self.group.teleports.len());
+    }
+
+    pub fn navigate_down(&mut self) {
+        select_next(&mut self.list_state, self.group.teleports.len());
+    }
+
+    /// Move the selected teleport one position up or down
+    pub fn move_selected(&mut self, up: bool) {
+        let Some(i) = self.list_state.selected() else {
+            return;
+        };
+        let target = if up {
+            if i == 0 {
+                return;
+            }
+            i - 1
+        } else {
+            if i + 1 >= self.group.teleports.len() {
+                return;
+            }
+            i + 1
+        };
+        self.group.teleports.swap(i, target);
+        self.list_state.select(Some(target));
+        self.dirty = true;
+    }
+
+    pub fn delete_selected(&mut self) {
+        let Some(i) = self.list_state.selected() else {
+            return;
+        };
+        if i >= self.group.teleports.len() {
+            return;
+        }
+        self.group.teleports.remove(i);
+        self.dirty = true;
+        if self.group.teleports.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state
+                .select(Some(i.min(self.group.teleports.len() - 1)));
+        }
+    }
+
+    /// Open the form for a new teleport
+    pub fn start_add(&mut self) {
+        let map = self
+            .list_state
+            .selected()
+            .and_then(|i| self.group.teleports.get(i))
+            .map(|t| t.map)
+            .unwrap_or_default();
+        self.form = Some(TeleportForm::new(None, Teleport::new("", map, [0.0; 3])));
+    }
+
+    /// Open the form for the selected teleport
+    pub fn start_edit(&mut self) {
+        if let Some(i) = self.list_state.selected()
+            && let Some(teleport) = self.group.teleports.get(i)
+        {
+            self.form = Some(TeleportForm::new(Some(i), teleport.clone()));
+        }
+    }
+
+    /// Validate the open form and apply it to the working copy
+    ///
+    /// The form stays open with an error message if validation fails.
+    pub fn submit_form(&mut self) {
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+        let teleport = match form.to_teleport() {
+            Ok(teleport) => teleport,
+            Err(e) => {
+                form.error = Some(e);
+                return;
+            }
+        };
+
+        match form.index {
+            Some(i) if i < self.group.teleports.len() => {
+                self.group.teleports[i] = teleport;
+                self.list_state.select(Some(i));
+            }
+            _ => {
+                let i = self
+                    .list_state
+                    .selected()
+                    .map(|i| i + 1)
+                    .unwrap_or(self.group.teleports.len())
+                    .min(self.group.teleports.len());
+                self.group.teleports.insert(i, teleport);
+                self.list_state.select(Some(i));
+            }
+        }
+        self.form = None;
+        self.dirty = true;
+    }
+
+    pub fn cancel_form(&mut self) {
+        self.form = None;
+    }
+}
+
+impl TeleportForm {
+    fn new(index: Option<usize>, base: Teleport) -> Self {
+        let values = [
+            base.name.clone(),
+            base.tooltip.clone().unwrap_or_default(),
+            base.map.to_string(),
+            base.coordinates[0].to_string(),
+            base.coordinates[1].to_string(),
+            base.coordinates[2].to_string(),
+        ];
+        Self {
+            index,
+            values,
+            focused: 0,
+            error: None,
+            base,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % FORM_FIELDS.len();
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = (self.focused + FORM_FIELDS.len() - 1) % FORM_FIELDS.len();
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.values[self.focused].push(c);
+        self.error = None;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.values[self.focused].pop();
+        self.error = None;
+    }
+
+    /// Parse the inputs into a teleport, keeping any extra fields of the original
+    fn to_teleport(&self) -> Result<Teleport, String> {
+        let name = self.values[0].trim();
+        if name.is_empty() {
+            return Err("Name must not be empty".to_string());
+        }
+
+        let map = self.values[2]
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("Map must be a map id, got '{}'", self.values[2]))?;
+
+        let mut coordinates = [0.0f32; 3];
+        for (axis, value) in coordinates.iter_mut().enumerate() {
+            let label = FORM_FIELDS[3 + axis];
+            let input = self.values[3 + axis].trim();
+            *value = input
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("{} must be a finite number, got '{}'", label, input))?;
+        }
+
+        let tooltip = self.values[1].trim();
+        let mut teleport = self.base.clone();
+        teleport.name = name.to_string();
+        teleport.tooltip = (!tooltip.is_empty()).then(|| tooltip.to_string());
+        teleport.map = map;
+        teleport.coordinates = coordinates;
+        Ok(teleport)
+    }
+}
@@ -1,57 +1,257 @@
 use crate::Result;
-use crate::ui::{App, AppMode};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::duplicates::DuplicateCluster;
+use crate::keymap::{Action, KeyScope};
+use crate::ui::{App, AppMode, Pane};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::time::{Duration, Instant};
+
+/// Two clicks on the same row within this interval count as a double-click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
 
 pub enum AppEvent {
     Quit,
 }
 
-pub async fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    if app.help_open {
+        return handle_help(app, key);
+    }
+    // The help key is just text while typing a search or a teleport field
+    let typing = matches!(app.mode, AppMode::Search)
+        || app.editor.as_ref().is_some_and(|e| e.form.is_some());
+    if !typing && app.keymap.action(&key) == Some(Action::Help) {
+        app.open_help();
+        return Ok(None);
+    }
+
     match app.mode {
-        AppMode::Normal => handle_normal_mode(app, key).await,
-        AppMode::ConfirmDelete(ref name) => handle_confirm_delete(app, key, name.clone()).await,
-        AppMode::ConfirmInstall(ref name) => handle_confirm_install(app, key, name.clone()).await,
+        AppMode::Normal => handle_normal_mode(app, key),
+        AppMode::ConfirmDelete(ref names) => handle_confirm_delete(app, key, names.clone()),
+        AppMode::ConfirmInstall(ref names) => handle_confirm_install(app, key, names.clone()),
+        AppMode::ConfirmDedupe(ref clusters) => handle_confirm_dedupe(app, key, clusters.clone()),
+        AppMode::EditGroup => handle_edit_group(app, key),
+        AppMode::ViewGroup(_) => handle_view_group(app, key),
+        AppMode::PickTeleports => handle_pick_teleports(app, key),
+        AppMode::ConfirmSplit(ref name) => handle_confirm_split(app, key, name.clone()),
+        AppMode::ConfirmMerge(ref names) => handle_confirm_merge(app, key, names.clone()),
+        AppMode::Search => handle_search(app, key),
+        AppMode::ViewDiff => handle_view_diff(app, key),
     }
 }
 
-async fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
-    match key.code {
-        KeyCode::Char('q') => Ok(Some(AppEvent::Quit)),
-        KeyCode::Tab => {
-            app.switch_pane();
-            Ok(None)
+pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent) -> Result<Option<AppEvent>> {
+    let position = Position::new(mouse.column, mouse.row);
+    if app.help_open {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => app.scroll_help(true),
+            MouseEventKind::ScrollDown => app.scroll_help(false),
+            _ => {}
         }
-        KeyCode::Up => {
-            app.navigate_up();
+        return Ok(None);
+    }
+    match app.mode {
+        AppMode::Normal | AppMode::Search => handle_mouse_lists(app, mouse, position),
+        AppMode::ViewGroup(_) | AppMode::ViewDiff => {
+            match mouse.kind {
+                MouseEventKind::ScrollUp => app.scroll_details(true),
+                MouseEventKind::ScrollDown => app.scroll_details(false),
+                _ => {}
+            }
             Ok(None)
         }
-        KeyCode::Down => {
-            app.navigate_down();
+        AppMode::EditGroup | AppMode::PickTeleports => {
+            let up = match mouse.kind {
+                MouseEventKind::ScrollUp => true,
+                MouseEventKind::ScrollDown => false,
+                _ => return Ok(None),
+            };
+            if let Some(editor) = app.editor.as_mut().filter(|e| e.form.is_none()) {
+                if up {
+                    editor.navigate_up();
+                } else {
+                    editor.navigate_down();
+                }
+            } else if let Some(picker) = app.picker.as_mut() {
+                if up {
+                    picker.navigate_up();
+                } else {
+                    picker.navigate_down();
+                }
+            }
             Ok(None)
         }
-        KeyCode::Delete => {
-            app.handle_delete();
+        AppMode::ConfirmDelete(_)
+        | AppMode::ConfirmInstall(_)
+        | AppMode::ConfirmDedupe(_)
+        | AppMode::ConfirmSplit(_)
+        | AppMode::ConfirmMerge(_) => {
+            if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                return Ok(None);
+            }
+            let hit = |area: Option<Rect>| area.is_some_and(|r| r.contains(position));
+            if hit(app.hit_areas.confirm_yes) {
+                answer_dialog(app)?;
+            } else if hit(app.hit_areas.confirm_no) {
+                app.cancel_confirmation();
+            }
             Ok(None)
         }
-        KeyCode::Char(' ') => {
-            app.handle_space();
-            Ok(None)
+    }
+}
+
+/// Accept the open confirmation dialog with its current choice, as its Yes button does
+fn answer_dialog(app: &mut App) -> Result<()> {
+    match app.mode.clone() {
+        AppMode::ConfirmDelete(names) => app.confirm_delete(names)?,
+        AppMode::ConfirmInstall(names) => app.confirm_install(names),
+        AppMode::ConfirmDedupe(clusters) => app.confirm_dedupe(clusters)?,
+        AppMode::ConfirmSplit(name) => app.confirm_split(name)?,
+        AppMode::ConfirmMerge(names) => app.confirm_merge(names)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Click to focus and select, double-click to install or delete, wheel to scroll
+fn handle_mouse_lists(
+    app: &mut App,
+    mouse: MouseEvent,
+    position: Position,
+) -> Result<Option<AppEvent>> {
+    if app.log_open && app.hit_areas.log.contains(position) {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => app.scroll_log(true, 1),
+            MouseEventKind::ScrollDown => app.scroll_log(false, 1),
+            _ => {}
         }
-        _ => Ok(None),
+        return Ok(None);
     }
+    let Some(pane) = app.pane_at(position) else {
+        return Ok(None);
+    };
+
+    match mouse.kind {
+        MouseEventKind::ScrollUp => app.scroll_pane(pane, true),
+        MouseEventKind::ScrollDown => app.scroll_pane(pane, false),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if matches!(app.mode, AppMode::Search) {
+                app.finish_search();
+            }
+            let Some(row) = app.row_at(&pane, position) else {
+                app.active_pane = pane;
+                return Ok(None);
+            };
+
+            let now = Instant::now();
+            let double = app
+                .last_click
+                .as_ref()
+                .is_some_and(|(at, last_pane, last_row)| {
+                    *last_pane == pane
+                        && *last_row == row
+                        && now.duration_since(*at) <= DOUBLE_CLICK
+                });
+            app.select_row(pane.clone(), row);
+            if double {
+                app.last_click = None;
+                match pane {
+                    Pane::Local => app.handle_delete(),
+                    Pane::Remote => app.handle_space(),
+                }
+            } else {
+                app.last_click = Some((now, pane, row));
+            }
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_help(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    match app.keymap.action(&key) {
+        Some(Action::Up) => app.scroll_help(true),
+        Some(Action::Down) => app.scroll_help(false),
+        Some(Action::Help | Action::Quit | Action::Details) => app.close_help(),
+        _ if app.keymap.action_in(KeyScope::Dialog, &key) == Some(Action::Cancel) => {
+            app.close_help()
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    let Some(action) = app.keymap.action(&key) else {
+        return Ok(None);
+    };
+
+    match action {
+        Action::Quit => return Ok(Some(AppEvent::Quit)),
+        Action::SwitchPane => app.switch_pane(),
+        Action::MoveUp => app.move_selected_group(true)?,
+        Action::MoveDown => app.move_selected_group(false)?,
+        Action::Up => app.navigate_up(),
+        Action::Down => app.navigate_down(),
+        Action::Diff => app.open_diff(),
+        Action::Reload => app.reload_remote(),
+        Action::Search => app.start_search(),
+        Action::ClearSearch => app.clear_search(),
+        Action::Delete => app.handle_delete(),
+        Action::Install => app.handle_space(),
+        Action::Mark => app.toggle_mark(),
+        Action::MarkAll => app.mark_all(),
+        Action::InvertMarks => app.invert_marks(),
+        Action::Dedupe => app.handle_dedupe(),
+        Action::Edit => app.open_editor(),
+        Action::Details => app.open_details(),
+        Action::Pick => app.open_picker(),
+        Action::Duplicate => app.duplicate_selected_group()?,
+        Action::Split => app.handle_split_by_map(),
+        Action::SplitPick => app.open_split_picker(),
+        Action::Merge => app.handle_merge(),
+        Action::Export => app.export_selected_group(),
+        Action::ExportMarkerPack => app.export_marker_pack(),
+        Action::ExportCsv => app.export_csv(),
+        Action::ImportCsv => app.import_csv(),
+        Action::ExportBundle => app.export_bundle(),
+        Action::ToggleLog => app.toggle_log(),
+        Action::ScrollLogUp => app.page_log(true),
+        Action::ScrollLogDown => app.page_log(false),
+        Action::Help => app.open_help(),
+        // Only bound in dialogs, the editor, the picker or while searching
+        Action::Confirm
+        | Action::Cancel
+        | Action::PrevChoice
+        | Action::NextChoice
+        | Action::ApplyChoice
+        | Action::EditorAdd
+        | Action::EditorEdit
+        | Action::EditorDelete
+        | Action::EditorMoveUp
+        | Action::EditorMoveDown
+        | Action::EditorSave
+        | Action::EditorDiscard
+        | Action::PickerToggle
+        | Action::PickerToggleAll
+        | Action::PickerInstall
+        | Action::PickerCancel
+        | Action::SearchDone => {}
+    }
+    Ok(None)
 }
 
-async fn handle_confirm_delete(
+fn handle_confirm_delete(
     app: &mut App,
     key: KeyEvent,
-    group_name: String,
+    names: Vec<String>,
 ) -> Result<Option<AppEvent>> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
-            app.confirm_delete(group_name).await?;
+    match app.keymap.action_in(KeyScope::Dialog, &key) {
+        Some(Action::Confirm) => {
+            app.confirm_delete(names)?;
             Ok(None)
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+        Some(Action::Cancel) => {
             app.cancel_confirmation();
             Ok(None)
         }
@@ -59,20 +259,175 @@ async fn handle_confirm_delete(
     }
 }
 
-async fn handle_confirm_install(
+fn handle_confirm_install(
+    app: &mut App,
+    key: KeyEvent,
+    names: Vec<String>,
+) -> Result<Option<AppEvent>> {
+    match app.keymap.action_in(KeyScope::Dialog, &key) {
+        Some(Action::PrevChoice) => app.cycle_install_policy(false),
+        Some(Action::NextChoice) => app.cycle_install_policy(true),
+        Some(Action::Confirm | Action::ApplyChoice) => app.confirm_install(names),
+        Some(Action::Cancel) => app.cancel_confirmation(),
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_confirm_dedupe(
+    app: &mut App,
+    key: KeyEvent,
+    clusters: Vec<DuplicateCluster>,
+) -> Result<Option<AppEvent>> {
+    match app.keymap.action_in(KeyScope::Dialog, &key) {
+        Some(Action::PrevChoice | Action::NextChoice) => app.cycle_dedupe_mode(),
+        Some(Action::Confirm | Action::ApplyChoice) => app.confirm_dedupe(clusters)?,
+        Some(Action::Cancel) => app.cancel_confirmation(),
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_view_group(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    match app.keymap.action(&key) {
+        Some(Action::Up) => app.scroll_details(true),
+        Some(Action::Down) => app.scroll_details(false),
+        Some(Action::Install) => {
+            app.cancel_confirmation();
+            app.handle_space();
+        }
+        Some(Action::Pick) => {
+            app.cancel_confirmation();
+            app.open_picker();
+        }
+        Some(Action::Quit | Action::Details) => app.cancel_confirmation(),
+        _ if app.keymap.action_in(KeyScope::Dialog, &key) == Some(Action::Cancel) => {
+            app.cancel_confirmation()
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_view_diff(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    match app.keymap.action(&key) {
+        Some(Action::Up) => app.scroll_details(true),
+        Some(Action::Down) => app.scroll_details(false),
+        Some(Action::Install) => app.install_from_diff(),
+        Some(Action::Quit | Action::Details) => app.close_diff(),
+        _ if app.keymap.action_in(KeyScope::Dialog, &key) == Some(Action::Cancel) => {
+            app.close_diff()
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_edit_group(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    let Some(editor) = app.editor.as_mut() else {
+        app.cancel_editor();
+        return Ok(None);
+    };
+
+    if let Some(form) = editor.form.as_mut() {
+        match key.code {
+            KeyCode::Esc => editor.cancel_form(),
+            KeyCode::Enter => editor.submit_form(),
+            KeyCode::Tab | KeyCode::Down => form.focus_next(),
+            KeyCode::BackTab | KeyCode::Up => form.focus_prev(),
+            KeyCode::Backspace => form.pop_char(),
+            KeyCode::Char(c) => form.push_char(c),
+            _ => {}
+        }
+        return Ok(None);
+    }
+
+    match app.keymap.action_in(KeyScope::Editor, &key) {
+        Some(Action::EditorMoveUp) => editor.move_selected(true),
+        Some(Action::EditorMoveDown) => editor.move_selected(false),
+        Some(Action::Up) => editor.navigate_up(),
+        Some(Action::Down) => editor.navigate_down(),
+        Some(Action::EditorAdd) => editor.start_add(),
+        Some(Action::EditorEdit) => editor.start_edit(),
+        Some(Action::EditorDelete) => editor.delete_selected(),
+        Some(Action::EditorSave) => app.save_editor()?,
+        Some(Action::EditorDiscard) => app.cancel_editor(),
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_confirm_split(
     app: &mut App,
     key: KeyEvent,
     group_name: String,
 ) -> Result<Option<AppEvent>> {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
-            app.confirm_install(group_name).await?;
+    match app.keymap.action_in(KeyScope::Dialog, &key) {
+        Some(Action::Confirm) => {
+            app.confirm_split(group_name)?;
             Ok(None)
         }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+        Some(Action::Cancel) => {
             app.cancel_confirmation();
             Ok(None)
         }
         _ => Ok(None),
     }
 }
+
+fn handle_confirm_merge(
+    app: &mut App,
+    key: KeyEvent,
+    names: Vec<String>,
+) -> Result<Option<AppEvent>> {
+    match app.keymap.action_in(KeyScope::Dialog, &key) {
+        Some(Action::PrevChoice) => app.cycle_merge_target(false),
+        Some(Action::NextChoice) => app.cycle_merge_target(true),
+        Some(Action::Confirm | Action::ApplyChoice) => app.confirm_merge(names)?,
+        Some(Action::Cancel) => app.cancel_confirmation(),
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_pick_teleports(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    let Some(picker) = app.picker.as_mut() else {
+        app.cancel_picker();
+        return Ok(None);
+    };
+
+    match app.keymap.action_in(KeyScope::Picker, &key) {
+        Some(Action::Up) => picker.navigate_up(),
+        Some(Action::Down) => picker.navigate_down(),
+        Some(Action::PrevChoice) => picker.cycle_target(false),
+        Some(Action::NextChoice) => picker.cycle_target(true),
+        Some(Action::PickerToggle) => {
+            picker.toggle_selected();
+            picker.navigate_down();
+        }
+        Some(Action::PickerToggleAll) => picker.toggle_all(),
+        Some(Action::PickerInstall) => app.confirm_pick()?,
+        Some(Action::PickerCancel) => app.cancel_picker(),
+        _ => {}
+    }
+    Ok(None)
+}
+
+fn handle_search(app: &mut App, key: KeyEvent) -> Result<Option<AppEvent>> {
+    match app.keymap.action_in(KeyScope::Search, &key) {
+        Some(Action::ClearSearch) => app.clear_search(),
+        Some(Action::SearchDone) => app.finish_search(),
+        Some(Action::Up) => app.navigate_up(),
+        Some(Action::Down) => app.navigate_down(),
+        Some(Action::SwitchPane) => app.switch_pane(),
+        // Anything else is typed into the query
+        _ => match key.code {
+            KeyCode::Backspace => app.pop_search_char(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.push_search_char(c)
+            }
+            _ => {}
+        },
+    }
+    Ok(None)
+}
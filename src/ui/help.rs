@@ -0,0 +1,251 @@
+use crate::keymap::{Action, fixed_label};
+use crate::ui::{App, AppMode, Pane};
+use crossterm::event::KeyCode;
+
+/// A titled list of key or symbol explanations in the help overlay
+pub struct HelpSection {
+    pub title: String,
+    /// Key or symbol, and what it means
+    pub entries: Vec<(String, String)>,
+}
+
+/// Actions on the main screen that work in either pane
+const COMMON_ACTIONS: [Action; 17] = [
+    Action::SwitchPane,
+    Action::Up,
+    Action::Down,
+    Action::Details,
+    Action::Diff,
+    Action::Search,
+    Action::ClearSearch,
+    Action::Mark,
+    Action::MarkAll,
+    Action::InvertMarks,
+    Action::Reload,
+    Action::ExportBundle,
+    Action::ExportCsv,
+    Action::ImportCsv,
+    Action::ToggleLog,
+    Action::ScrollLogUp,
+    Action::ScrollLogDown,
+];
+
+const LOCAL_ACTIONS: [Action; 11] = [
+    Action::Delete,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::Edit,
+    Action::Duplicate,
+    Action::Split,
+    Action::SplitPick,
+    Action::Merge,
+    Action::Dedupe,
+    Action::Export,
+    Action::ExportMarkerPack,
+];
+
+const REMOTE_ACTIONS: [Action; 2] = [Action::Install, Action::Pick];
+
+/// Help for the current mode and pane, then the status icons and file locations
+pub fn help_sections(app: &App) -> Vec<HelpSection> {
+    vec![
+        key_section(app),
+        HelpSection {
+            title: "Status icons".to_string(),
+            entries: entries(&[
+                ("✓", "Installed locally, or a finished action"),
+                ("○", "Available remotely, or nothing to do"),
+                ("⇄", "Remote group with the name of a local group"),
+                ("↑", "Remote version differs, update available"),
+                ("=", "Remote version matches the local group"),
+                ("●", "Marked for a batch install or delete"),
+                ("✗", "Something failed, see the message"),
+                ("⚠", "Suspicious data, like unknown maps or coordinates"),
+                (">>", "Selected row"),
+                ("⠋", "Remote work running in the background"),
+                ("+ - ~", "Added, removed and modified teleports in a diff"),
+            ]),
+        },
+        HelpSection {
+            title: "Files and remotes".to_string(),
+            entries: [(
+                "teleport.json".to_string(),
+                app.teleport_json_path.display().to_string(),
+            )]
+            .into_iter()
+            .chain(
+                app.remote_sources
+                    .iter()
+                    .map(|source| ("Remote".to_string(), source.clone())),
+            )
+            .chain([
+                (
+                    "Config".to_string(),
+                    app.config_path
+                        .as_ref()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                ),
+                ("Exports".to_string(), app.export_dir.display().to_string()),
+                (
+                    "Activity log".to_string(),
+                    app.log
+                        .file()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| "not written to a file".to_string()),
+                ),
+            ])
+            .collect(),
+        },
+    ]
+}
+
+fn key_section(app: &App) -> HelpSection {
+    let keymap = &app.keymap;
+    let actions = |actions: &[Action]| -> Vec<(String, String)> {
+        actions
+            .iter()
+            .map(|a| (keymap.label(*a), a.description().to_string()))
+            .collect()
+    };
+    let answer = || actions(&[Action::Confirm, Action::Cancel]);
+    let choose = (
+        format!(
+            "{}/{}",
+            keymap.label(Action::PrevChoice),
+            keymap.label(Action::NextChoice)
+        ),
+        "Choose".to_string(),
+    );
+    let navigate = || {
+        (
+            format!(
+                "{}/{}",
+                keymap.label(Action::Up),
+                keymap.label(Action::Down)
+            ),
+            "Navigate".to_string(),
+        )
+    };
+    let scroll = || {
+        (
+            format!(
+                "{}/{}",
+                keymap.label(Action::Up),
+                keymap.label(Action::Down)
+            ),
+            "Scroll".to_string(),
+        )
+    };
+
+    let (title, entries) = match &app.mode {
+        AppMode::Normal => {
+            let (title, pane_actions): (&str, &[Action]) = match app.active_pane {
+                Pane::Local => ("Keys: local groups", &LOCAL_ACTIONS),
+                Pane::Remote => ("Keys: remote groups", &REMOTE_ACTIONS),
+            };
+            let mut entries = actions(pane_actions);
+            entries.extend(actions(&COMMON_ACTIONS));
+            entries.extend(actions(&[Action::Help, Action::Quit]));
+            entries.extend(self::entries(&[
+                ("Click", "Select a group and focus its pane"),
+                (
+                    "Double-click",
+                    "Install a remote group or delete a local one",
+                ),
+                ("Wheel", "Move the selection, or scroll the log"),
+            ]));
+            (title, entries)
+        }
+        AppMode::Search => {
+            let mut entries = self::entries(&[("Type", "Filter both panes by name")]);
+            entries.push(navigate());
+            entries.extend(actions(&[
+                Action::SwitchPane,
+                Action::SearchDone,
+                Action::ClearSearch,
+            ]));
+            ("Keys: search", entries)
+        }
+        AppMode::EditGroup if app.editor.as_ref().is_some_and(|e| e.form.is_some()) => (
+            "Keys: teleport form",
+            vec![
+                (
+                    fixed_label(&[KeyCode::Tab, KeyCode::Down]),
+                    "Next field".to_string(),
+                ),
+                (
+                    fixed_label(&[KeyCode::BackTab, KeyCode::Up]),
+                    "Previous field".to_string(),
+                ),
+                (fixed_label(&[KeyCode::Enter]), "Apply".to_string()),
+                (fixed_label(&[KeyCode::Esc]), "Cancel".to_string()),
+            ],
+        ),
+        AppMode::EditGroup => {
+            let mut entries = vec![navigate()];
+            entries.extend(actions(&[
+                Action::EditorAdd,
+                Action::EditorEdit,
+                Action::EditorDelete,
+                Action::EditorMoveUp,
+                Action::EditorMoveDown,
+                Action::EditorSave,
+                Action::EditorDiscard,
+            ]));
+            ("Keys: group editor", entries)
+        }
+        AppMode::PickTeleports => {
+            let mut entries = vec![navigate()];
+            entries.extend(actions(&[Action::PickerToggle]));
+            entries.push((
+                format!(
+                    "{}/{}",
+                    keymap.label(Action::PrevChoice),
+                    keymap.label(Action::NextChoice)
+                ),
+                "Choose the target group".to_string(),
+            ));
+            entries.extend(actions(&[
+                Action::PickerToggleAll,
+                Action::PickerInstall,
+                Action::PickerCancel,
+            ]));
+            ("Keys: teleport picker", entries)
+        }
+        AppMode::ViewGroup(_) => {
+            let mut entries = vec![scroll()];
+            entries.extend(actions(&[Action::Install, Action::Pick]));
+            entries.push((keymap.label(Action::Cancel), "Close".to_string()));
+            ("Keys: group details", entries)
+        }
+        AppMode::ViewDiff => {
+            let mut entries = vec![scroll()];
+            entries.push((
+                keymap.label(Action::Install),
+                "Overwrite the local group".to_string(),
+            ));
+            entries.push((keymap.label(Action::Cancel), "Close".to_string()));
+            ("Keys: diff", entries)
+        }
+        AppMode::ConfirmInstall(_) | AppMode::ConfirmMerge(_) | AppMode::ConfirmDedupe(_) => {
+            let mut entries = vec![choose];
+            entries.extend(actions(&[Action::ApplyChoice]));
+            entries.extend(answer());
+            ("Keys: confirmation", entries)
+        }
+        AppMode::ConfirmDelete(_) | AppMode::ConfirmSplit(_) => ("Keys: confirmation", answer()),
+    };
+
+    HelpSection {
+        title: title.to_string(),
+        entries,
+    }
+}
+
+fn entries(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(key, text)| (key.to_string(), text.to_string()))
+        .collect()
+}
@@ -0,0 +1,25 @@
+use ratatui::widgets::ListState;
+
+/// Select the previous row of a list of `len` rows, wrapping to the last
+pub fn select_prev(state: &mut ListState, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let i = match state.selected() {
+        Some(0) | None => len - 1,
+        Some(i) => i - 1,
+    };
+    state.select(Some(i));
+}
+
+/// Select the next row of a list of `len` rows, wrapping to the first
+pub fn select_next(state: &mut ListState, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let i = match state.selected() {
+        Some(i) if i + 1 < len => i + 1,
+        _ => 0,
+    };
+    state.select(Some(i));
+}
@@ -1,7 +1,18 @@
 mod app;
+mod editor;
 mod events;
+mod help;
+mod list;
+mod picker;
 mod render;
+mod search;
+mod tasks;
 
-pub use app::{App, AppMode, Pane};
-pub use events::{AppEvent, handle_key_event};
+pub use app::{App, AppMode, DiffView, HitAreas, Pane};
+pub use editor::{FORM_FIELDS, GroupEditor, TeleportForm};
+pub use events::{AppEvent, handle_key_event, handle_mouse_event};
+pub use help::{HelpSection, help_sections};
+pub use picker::TeleportPicker;
 pub use render::render;
+pub use search::{FuzzyMatch, filter_names, fuzzy_match};
+pub use tasks::{FetchPurpose, TaskResult, TaskRunner};
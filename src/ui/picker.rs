@@ -0,0 +1,103 @@
+use crate::sync::PickTarget;
+use crate::types::TeleportGroup;
+use crate::ui::list::{select_next, select_prev};
+use ratatui::widgets::ListState;
+
+/// Selection of individual teleports from a remote group
+pub struct TeleportPicker {
+    /// Remote name the group was fetched under
+    pub remote_name: String,
+    pub group: TeleportGroup,
+    pub list_state: ListState,
+    pub selected: Vec<bool>,
+    /// Local groups the selection can be merged into
+    pub local_groups: Vec<String>,
+    /// 0 installs as a new group, i > 0 merges into `local_groups[i - 1]`
+    pub target: usize,
+    /// Picking from a local group to split the selection off into a new group
+    pub split: bool,
+}
+
+impl TeleportPicker {
+    pub fn new(remote_name: String, group: TeleportGroup, local_groups: Vec<String>) -> Self {
+        let mut list_state = ListState::default();
+        if !group.teleports.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            remote_name,
+            selected: vec![false; group.teleports.len()],
+            group,
+            list_state,
+            local_groups,
+            target: 0,
+            split: false,
+        }
+    }
+
+    /// Picker over a local group whose selection is split off into a new group
+    pub fn for_split(group: TeleportGroup) -> Self {
+        let mut picker = Self::new(group.name.clone(), group, Vec::new());
+        picker.split = true;
+        picker
+    }
+
+    pub fn navigate_up(&mut self) {
+        select_prev(&mut self.list_state, self.group.teleports.len());
+    }
+
+    pub fn navigate_down(&mut self) {
+        select_next(&mut self.list_state, self.group.teleports.len());
+    }
+
+    pub fn toggle_selected(&mut self) {
+        if let Some(i) = self.list_state.selected()
+            && let Some(flag) = self.selected.get_mut(i)
+        {
+            *flag = !*flag;
+        }
+    }
+
+    /// Select everything, or nothing if everything is already selected
+    pub fn toggle_all(&mut self) {
+        let all = self.selected.iter().all(|s| *s);
+        self.selected.iter_mut().for_each(|s| *s = !all);
+    }
+
+    pub fn cycle_target(&mut self, forward: bool) {
+        let len = self.local_groups.len() + 1;
+        self.target = if forward {
+            (self.target + 1) % len
+        } else {
+            (self.target + len - 1) % len
+        };
+    }
+
+    /// Indices of the picked teleports
+    pub fn picked_indices(&self) -> Vec<usize> {
+        self.selected
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| **s)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn pick_target(&self) -> PickTarget {
+        match self.target {
+            0 => PickTarget::NewGroup(self.group.name.clone()),
+            i => PickTarget::ExistingGroup(self.local_groups[i - 1].clone()),
+        }
+    }
+
+    /// Human readable description of the install target
+    pub fn target_label(&self) -> String {
+        if self.split {
+            return format!("New group split from '{}'", self.group.name);
+        }
+        match self.pick_target() {
+            PickTarget::NewGroup(name) => format!("New group '{}'", name),
+            PickTarget::ExistingGroup(name) => format!("Merge into '{}'", name),
+        }
+    }
+}
@@ -1,8 +1,18 @@
-use crate::ui::{App, AppMode, Pane};
+use crate::activity::LogLevel;
+use crate::diff::TeleportDiff;
+use crate::duplicates::DedupeMode;
+use crate::keymap::{Action, fixed_label};
+use crate::manager::ConflictPolicy;
+use crate::maps::MapDatabase;
+use crate::theme::Theme;
+use crate::types::{Teleport, TeleportGroup};
+use crate::ui::{App, AppMode, FORM_FIELDS, HitAreas, Pane, fuzzy_match, help_sections};
+use crate::validation::{CoordinateChecker, CoordinateWarning};
+use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
 };
@@ -17,20 +27,39 @@ pub fn render(f: &mut Frame, app: &mut App) {
         ])
         .split(f.area());
 
-    render_title(f, chunks[0]);
-    render_main_content(f, chunks[1], app);
+    // Filled in again by whatever this frame draws
+    app.hit_areas = HitAreas::default();
+    render_title(f, chunks[0], &app.theme);
+    if matches!(app.mode, AppMode::EditGroup) {
+        render_group_editor(f, chunks[1], app);
+    } else if matches!(app.mode, AppMode::PickTeleports) {
+        render_teleport_picker(f, chunks[1], app);
+    } else {
+        render_main_content(f, chunks[1], app);
+    }
     render_status_bar(f, chunks[2], app);
 
-    if !matches!(app.mode, AppMode::Normal) {
+    if matches!(app.mode, AppMode::ViewGroup(_)) {
+        render_group_popup(f, app);
+    } else if matches!(app.mode, AppMode::ViewDiff) {
+        render_diff_popup(f, app);
+    } else if !matches!(
+        app.mode,
+        AppMode::Normal | AppMode::EditGroup | AppMode::PickTeleports | AppMode::Search
+    ) {
         render_confirmation_dialog(f, app);
     }
+
+    if app.help_open {
+        render_help_popup(f, app);
+    }
 }
 
-fn render_title(f: &mut Frame, area: Rect) {
+fn render_title(f: &mut Frame, area: Rect, theme: &Theme) {
     let title = Paragraph::new("Teleport Sync Manager")
         .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.title)
                 .add_modifier(Modifier::BOLD),
         )
         .block(Block::default().borders(Borders::ALL));
@@ -38,26 +67,106 @@ fn render_title(f: &mut Frame, area: Rect) {
 }
 
 fn render_main_content(f: &mut Frame, area: Rect, app: &mut App) {
+    let log_height = if app.log_open { 10 } else { 0 };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(8),
+            Constraint::Length(log_height),
+        ])
+        .split(area);
+
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(area);
+        .split(rows[0]);
 
+    app.hit_areas.local_list = chunks[0];
+    app.hit_areas.remote_list = chunks[1];
     render_local_pane(f, chunks[0], app);
     render_remote_pane(f, chunks[1], app);
+    render_group_details(f, rows[1], app);
+    app.hit_areas.log = rows[2];
+    if app.log_open {
+        render_activity_log(f, rows[2], app);
+    }
+}
+
+/// Activity log entries up to the scroll position, newest at the bottom
+fn render_activity_log(f: &mut Frame, area: Rect, app: &App) {
+    let shown = usize::from(area.height.saturating_sub(2));
+    let mut lines: Vec<Line> = app
+        .log
+        .entries()
+        .rev()
+        .skip(app.log_scroll)
+        .take(shown)
+        .map(|entry| {
+            let color = match entry.level {
+                LogLevel::Info => app.theme.text,
+                LogLevel::Success => app.theme.added,
+                LogLevel::Warning => app.theme.modified,
+                LogLevel::Error => app.theme.error,
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", entry.clock()),
+                    Style::default().fg(app.theme.muted),
+                ),
+                Span::styled(entry.message.as_str(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    lines.reverse();
+
+    let mut title = format!(
+        "Activity Log, UTC ({}/{} to scroll, {} to hide",
+        app.keymap.label(Action::ScrollLogUp),
+        app.keymap.label(Action::ScrollLogDown),
+        app.keymap.label(Action::ToggleLog)
+    );
+    if let Some(path) = app.log.file() {
+        title.push_str(&format!(", full log in {}", path.display()));
+    }
+    title.push(')');
+    if app.log_scroll > 0 {
+        title.push_str(&format!(" {} newer", app.log_scroll));
+    }
+    let log = Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL));
+
+    f.render_widget(log, area);
 }
 
 fn render_local_pane(f: &mut Frame, area: Rect, app: &mut App) {
     let is_active = matches!(app.active_pane, Pane::Local);
 
+    let symbols: Vec<&str> = app
+        .local_view
+        .iter()
+        .map(|&i| match app.local_changed(&app.local_groups[i]) {
+            Some(true) => "↑ ",
+            _ => "✓ ",
+        })
+        .collect();
     let items: Vec<ListItem> = app
-        .local_groups
+        .local_view
         .iter()
-        .map(|name| ListItem::new(Line::from(vec![Span::raw("✓ "), Span::raw(name)])))
+        .zip(symbols)
+        .map(|(&i, symbol)| {
+            let name = &app.local_groups[i];
+            group_item(
+                symbol,
+                name,
+                &app.search_query,
+                app.local_marked.contains(name),
+                &app.theme,
+            )
+        })
         .collect();
 
     let border_style = if is_active {
-        Style::default().fg(Color::Green)
+        app.theme.focused_border()
     } else {
         Style::default()
     };
@@ -65,15 +174,19 @@ fn render_local_pane(f: &mut Frame, area: Rect, app: &mut App) {
     let list = List::new(items)
         .block(
             Block::default()
-                .title("Local Groups (DEL to remove)")
+                .title(pane_title(
+                    &format!(
+                        "Local Groups ({} to remove)",
+                        app.keymap.label(Action::Delete)
+                    ),
+                    app.local_view.len(),
+                    app.local_groups.len(),
+                    app.local_marked.len(),
+                ))
                 .borders(Borders::ALL)
                 .border_style(border_style),
         )
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(app.theme.selected())
         .highlight_symbol(">> ");
 
     f.render_stateful_widget(list, area, &mut app.local_list_state);
@@ -82,14 +195,37 @@ fn render_local_pane(f: &mut Frame, area: Rect, app: &mut App) {
 fn render_remote_pane(f: &mut Frame, area: Rect, app: &mut App) {
     let is_active = matches!(app.active_pane, Pane::Remote);
 
+    let symbols: Vec<&str> = app
+        .remote_view
+        .iter()
+        .map(|&i| {
+            let name = &app.remote_groups[i];
+            match (app.local_conflict(name), app.remote_changed(name)) {
+                (Some(_), Some(true)) => "↑ ",
+                (Some(_), Some(false)) => "= ",
+                (Some(_), None) => "⇄ ",
+                (None, _) => "○ ",
+            }
+        })
+        .collect();
     let items: Vec<ListItem> = app
-        .remote_groups
+        .remote_view
         .iter()
-        .map(|name| ListItem::new(Line::from(vec![Span::raw("○ "), Span::raw(name)])))
+        .zip(symbols)
+        .map(|(&i, symbol)| {
+            let name = &app.remote_groups[i];
+            group_item(
+                symbol,
+                name,
+                &app.search_query,
+                app.remote_marked.contains(name),
+                &app.theme,
+            )
+        })
         .collect();
 
     let border_style = if is_active {
-        Style::default().fg(Color::Green)
+        app.theme.focused_border()
     } else {
         Style::default()
     };
@@ -97,64 +233,911 @@ fn render_remote_pane(f: &mut Frame, area: Rect, app: &mut App) {
     let list = List::new(items)
         .block(
             Block::default()
-                .title("Available Remote Groups (SPACE to install)")
+                .title(pane_title(
+                    &format!(
+                        "Available Remote Groups ({} to install)",
+                        app.keymap.label(Action::Install)
+                    ),
+                    app.remote_view.len(),
+                    app.remote_groups.len(),
+                    app.remote_marked.len(),
+                ))
                 .borders(Borders::ALL)
                 .border_style(border_style),
         )
-        .highlight_style(
+        .highlight_style(app.theme.selected())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, area, &mut app.remote_list_state);
+}
+
+/// Pane title with the number of search matches and marked groups, if any
+fn pane_title(title: &str, shown: usize, total: usize, marked: usize) -> String {
+    let mut title = title.to_string();
+    if shown != total {
+        title.push_str(&format!(" [{}/{}]", shown, total));
+    }
+    if marked > 0 {
+        title.push_str(&format!(" [{} marked]", marked));
+    }
+    title
+}
+
+/// List row for a group name with the characters matching the search highlighted
+fn group_item<'a>(
+    symbol: &'a str,
+    name: &'a str,
+    query: &str,
+    marked: bool,
+    theme: &Theme,
+) -> ListItem<'a> {
+    let mut spans = vec![if marked {
+        Span::styled("● ", Style::default().fg(theme.marked))
+    } else {
+        Span::raw(symbol)
+    }];
+    let positions = fuzzy_match(query, name)
+        .map(|m| m.positions)
+        .unwrap_or_default();
+    if positions.is_empty() {
+        spans.push(Span::raw(name));
+        return ListItem::new(Line::from(spans));
+    }
+
+    let highlight = Style::default()
+        .fg(theme.matched)
+        .add_modifier(Modifier::BOLD);
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in name.chars().enumerate() {
+        let matched = positions.contains(&i);
+        if matched != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched {
+                Span::styled(text, highlight)
+            } else {
+                Span::raw(text)
+            });
+        }
+        run_matched = matched;
+        run.push(c);
+    }
+    spans.push(if run_matched {
+        Span::styled(run, highlight)
+    } else {
+        Span::raw(run)
+    });
+    ListItem::new(Line::from(spans))
+}
+
+fn render_group_details(f: &mut Frame, area: Rect, app: &App) {
+    let maps = MapDatabase::bundled();
+
+    let lines: Vec<Line> = match (app.selected_group_data(), &app.active_pane) {
+        (Some(group), _) => group_summary_lines(group, maps, &app.theme),
+        (None, Pane::Remote) if app.selected_group().is_some() => vec![Line::from(format!(
+            "Press {} to load details",
+            app.keymap.label(Action::Details)
+        ))],
+        (None, _) => Vec::new(),
+    };
+
+    let details = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(
+                "Group Details ({} for teleports)",
+                app.keymap.label(Action::Details)
+            ))
+            .borders(Borders::ALL),
+    );
+
+    f.render_widget(details, area);
+}
+
+/// Summary counts and per-map breakdown of a group
+fn group_summary_lines(
+    group: &TeleportGroup,
+    maps: &MapDatabase,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    // Count teleports per map, keeping first-seen order
+    let mut per_map: Vec<(u32, usize)> = Vec::new();
+    for teleport in &group.teleports {
+        match per_map.iter_mut().find(|(id, _)| *id == teleport.map) {
+            Some((_, count)) => *count += 1,
+            None => per_map.push((teleport.map, 1)),
+        }
+    }
+
+    let unknown = maps.unknown_teleports(group).len();
+    let suspicious = CoordinateChecker::new(maps)
+        .check_group(group)
+        .iter()
+        .filter(|w| w.issue.is_suspicious())
+        .count();
+    let with_tooltip = group
+        .teleports
+        .iter()
+        .filter(|t| t.tooltip.as_deref().is_some_and(|s| !s.is_empty()))
+        .count();
+
+    let mut lines = vec![Line::from(format!(
+        "{} teleports across {} maps, {} with tooltips",
+        group.teleports.len(),
+        per_map.len(),
+        with_tooltip
+    ))];
+    lines.push(Line::from(Span::styled(
+        format!("Digest: {}", group.digest()),
+        Style::default().fg(theme.muted),
+    )));
+    if unknown > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {} teleports reference unknown maps", unknown),
+            Style::default().fg(theme.error),
+        )));
+    }
+    if suspicious > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {} teleports have suspicious coordinates", suspicious),
+            Style::default().fg(theme.error),
+        )));
+    }
+    for (id, count) in per_map {
+        let style = if maps.contains(id) {
             Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+        } else {
+            Style::default().fg(theme.error)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("  {}: {}", maps.label(id), count),
+            style,
+        )));
+    }
+    lines
+}
+
+fn render_group_popup(f: &mut Frame, app: &App) {
+    let AppMode::ViewGroup(name) = &app.mode else {
+        return;
+    };
+    let maps = MapDatabase::bundled();
+
+    let mut lines = Vec::new();
+    match app.selected_group_data() {
+        Some(group) => {
+            lines.extend(group_summary_lines(group, maps, &app.theme));
+            lines.push(Line::from(""));
+            for (i, t) in group.teleports.iter().enumerate() {
+                let map_style = if maps.contains(t.map) {
+                    Style::default().fg(app.theme.accent)
+                } else {
+                    Style::default().fg(app.theme.error)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{:>3}. {}", i + 1, t.name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw("  "),
+                    Span::styled(maps.label(t.map), map_style),
+                    Span::raw(format!(
+                        "  ({:.1}, {:.1}, {:.1})",
+                        t.coordinates[0], t.coordinates[1], t.coordinates[2]
+                    )),
+                ]));
+                if let Some(tooltip) = t.tooltip.as_deref().filter(|s| !s.is_empty()) {
+                    for tooltip_line in tooltip.lines() {
+                        lines.push(Line::from(Span::styled(
+                            format!("       {}", tooltip_line),
+                            Style::default().fg(app.theme.muted),
+                        )));
+                    }
+                }
+            }
+        }
+        None => lines.push(Line::from("No details available")),
+    }
+
+    let keys = |action| app.keymap.label(action);
+    let popup_title = if matches!(app.active_pane, Pane::Remote) {
+        format!(
+            "{} ({}/{}: Scroll | {}: Install | {}: Pick | {}: Close)",
+            name,
+            keys(Action::Up),
+            keys(Action::Down),
+            keys(Action::Install),
+            keys(Action::Pick),
+            keys(Action::Cancel)
+        )
+    } else {
+        format!(
+            "{} ({}/{}: Scroll | {}: Close)",
+            name,
+            keys(Action::Up),
+            keys(Action::Down),
+            keys(Action::Cancel)
+        )
+    };
+
+    let area = centered_rect(80, 80, f.area());
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(popup_title)
+                .borders(Borders::ALL)
+                .style(Style::default().bg(app.theme.popup_background)),
         )
+        .style(Style::default().fg(app.theme.text))
+        .scroll((app.details_scroll, 0));
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// One-line description of a teleport for the diff columns
+fn teleport_summary(t: &Teleport, maps: &MapDatabase) -> String {
+    format!(
+        "{}  {}  ({:.1}, {:.1}, {:.1})",
+        t.name,
+        maps.label(t.map),
+        t.coordinates[0],
+        t.coordinates[1],
+        t.coordinates[2]
+    )
+}
+
+fn render_diff_popup(f: &mut Frame, app: &App) {
+    let Some(view) = &app.diff else {
+        return;
+    };
+    let maps = MapDatabase::bundled();
+    let added = Style::default().fg(app.theme.added);
+    let removed = Style::default().fg(app.theme.removed);
+    let modified = Style::default().fg(app.theme.modified);
+    let unchanged = Style::default().fg(app.theme.muted);
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+
+    let mut left = vec![
+        Line::from(Span::styled(format!("Local '{}'", view.local_name), bold)),
+        Line::from(""),
+    ];
+    let mut right = vec![
+        Line::from(Span::styled(format!("Remote '{}'", view.remote_name), bold)),
+        Line::from(""),
+    ];
+    if !view.diff.has_changes() {
+        left.push(Line::from("No differences"));
+        right.push(Line::from("No differences"));
+    }
+
+    for entry in &view.diff.entries {
+        match entry {
+            TeleportDiff::Added(t) => {
+                left.push(Line::from(""));
+                right.push(Line::from(Span::styled(
+                    format!("+ {}", teleport_summary(t, maps)),
+                    added,
+                )));
+            }
+            TeleportDiff::Removed(t) => {
+                left.push(Line::from(Span::styled(
+                    format!("- {}", teleport_summary(t, maps)),
+                    removed,
+                )));
+                right.push(Line::from(""));
+            }
+            TeleportDiff::Modified { old, new } => {
+                left.push(Line::from(Span::styled(
+                    format!("~ {}", teleport_summary(old, maps)),
+                    modified,
+                )));
+                right.push(Line::from(Span::styled(
+                    format!("~ {}", teleport_summary(new, maps)),
+                    modified,
+                )));
+                if let Some([dx, dy, dz]) = entry.coordinate_delta() {
+                    left.push(Line::from(""));
+                    right.push(Line::from(Span::styled(
+                        format!(
+                            "    Δ ({:+.1}, {:+.1}, {:+.1}), moved {:.1}",
+                            dx,
+                            dy,
+                            dz,
+                            (dx * dx + dy * dy + dz * dz).sqrt()
+                        ),
+                        modified,
+                    )));
+                }
+                if entry.tooltip_changed() {
+                    let tooltip = |t: &Teleport| {
+                        let text = t.tooltip.as_deref().unwrap_or("(none)");
+                        format!("    tooltip: {}", text.lines().next().unwrap_or(""))
+                    };
+                    left.push(Line::from(Span::styled(tooltip(old), removed)));
+                    right.push(Line::from(Span::styled(tooltip(new), added)));
+                }
+                if old.other_fields != new.other_fields {
+                    left.push(Line::from(""));
+                    right.push(Line::from(Span::styled(
+                        "    other fields changed",
+                        modified,
+                    )));
+                }
+            }
+            TeleportDiff::Unchanged(t) => {
+                let line = format!("  {}", teleport_summary(t, maps));
+                left.push(Line::from(Span::styled(line.clone(), unchanged)));
+                right.push(Line::from(Span::styled(line, unchanged)));
+            }
+        }
+    }
+
+    let (added_count, removed_count, modified_count) = view.diff.counts();
+    let title = format!(
+        "Diff: +{} added, -{} removed, ~{} modified ({}/{}: Scroll | {}: Overwrite local | {}: Close)",
+        added_count,
+        removed_count,
+        modified_count,
+        app.keymap.label(Action::Up),
+        app.keymap.label(Action::Down),
+        app.keymap.label(Action::Install),
+        app.keymap.label(Action::Cancel)
+    );
+    let area = centered_rect(90, 80, f.area());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.theme.popup_background));
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    for (lines, column) in [(left, columns[0]), (right, columns[1])] {
+        let paragraph = Paragraph::new(lines)
+            .style(Style::default().fg(app.theme.text))
+            .scroll((app.details_scroll, 0));
+        f.render_widget(paragraph, column);
+    }
+}
+
+fn render_group_editor(f: &mut Frame, area: Rect, app: &mut App) {
+    let Some(editor) = app.editor.as_mut() else {
+        return;
+    };
+    let maps = MapDatabase::bundled();
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let items: Vec<ListItem> = editor
+        .group
+        .teleports
+        .iter()
+        .map(|t| {
+            ListItem::new(Line::from(vec![
+                Span::raw(t.name.as_str()),
+                Span::styled(
+                    format!("  {}", maps.label(t.map)),
+                    Style::default().fg(app.theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let title = if editor.dirty {
+        format!("Editing '{}' (modified)", editor.group.name)
+    } else {
+        format!("Editing '{}'", editor.group.name)
+    };
+
+    let list_border = if editor.form.is_none() {
+        app.theme.focused_border()
+    } else {
+        Style::default()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(list_border),
+        )
+        .highlight_style(app.theme.selected())
         .highlight_symbol(">> ");
 
-    f.render_stateful_widget(list, area, &mut app.remote_list_state);
+    f.render_stateful_widget(list, chunks[0], &mut editor.list_state);
+
+    let (title, lines, border_style) = match &editor.form {
+        Some(form) => {
+            let mut lines: Vec<Line> = FORM_FIELDS
+                .iter()
+                .zip(&form.values)
+                .enumerate()
+                .map(|(i, (label, value))| {
+                    if i == form.focused {
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:>8}: ", label),
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ),
+                            Span::styled(
+                                format!("{}▏", value),
+                                Style::default().bg(app.theme.selection),
+                            ),
+                        ])
+                    } else {
+                        Line::from(format!("{:>8}: {}", label, value))
+                    }
+                })
+                .collect();
+            if let Ok(id) = form.values[2].trim().parse::<u32>() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(format!("Map: {}", maps.label(id))));
+            }
+            if let Some(error) = &form.error {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("✗ {}", error),
+                    Style::default().fg(app.theme.error),
+                )));
+            }
+            let title = if form.index.is_some() {
+                "Edit Teleport"
+            } else {
+                "New Teleport"
+            };
+            (title, lines, app.theme.focused_border())
+        }
+        None => {
+            let lines = match editor
+                .list_state
+                .selected()
+                .and_then(|i| editor.group.teleports.get(i))
+            {
+                Some(t) => vec![
+                    Line::from(format!("Name: {}", t.name)),
+                    Line::from(format!("Tooltip: {}", t.tooltip.as_deref().unwrap_or(""))),
+                    Line::from(format!("Map: {}", maps.label(t.map))),
+                    Line::from(format!(
+                        "Coordinates: {:.2}, {:.2}, {:.2}",
+                        t.coordinates[0], t.coordinates[1], t.coordinates[2]
+                    )),
+                ],
+                None => vec![Line::from(format!(
+                    "No teleports, press {} to add one",
+                    app.keymap.label(Action::EditorAdd)
+                ))],
+            };
+            ("Teleport", lines, Style::default())
+        }
+    };
+
+    let details = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+    f.render_widget(details, chunks[1]);
+}
+
+fn render_teleport_picker(f: &mut Frame, area: Rect, app: &mut App) {
+    let Some(picker) = app.picker.as_mut() else {
+        return;
+    };
+    let maps = MapDatabase::bundled();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    let items: Vec<ListItem> = picker
+        .group
+        .teleports
+        .iter()
+        .zip(&picker.selected)
+        .map(|(t, selected)| {
+            let mark = if *selected { "[x] " } else { "[ ] " };
+            ListItem::new(Line::from(vec![
+                Span::raw(mark),
+                Span::raw(t.name.as_str()),
+                Span::styled(
+                    format!("  {}", maps.label(t.map)),
+                    Style::default().fg(app.theme.muted),
+                ),
+            ]))
+        })
+        .collect();
+
+    let count = picker.selected.iter().filter(|s| **s).count();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Pick from '{}' ({} of {} selected)",
+                    picker.remote_name,
+                    count,
+                    picker.selected.len()
+                ))
+                .borders(Borders::ALL)
+                .border_style(app.theme.focused_border()),
+        )
+        .highlight_style(app.theme.selected())
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[0], &mut picker.list_state);
+
+    let target = Paragraph::new(format!("← {} →", picker.target_label()))
+        .block(Block::default().title("Install into").borders(Borders::ALL));
+    f.render_widget(target, chunks[1]);
 }
 
+/// Frames of the busy indicator shown while background work runs
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
-    let status_text = if app.is_loading {
-        "Loading..."
+    let keys = |action| app.keymap.label(action);
+    let editor_hint = app.editor.as_ref().map(|editor| {
+        if editor.form.is_some() {
+            format!(
+                "{}: Next field | {}: Previous field | {}: Apply | {}: Cancel",
+                fixed_label(&[KeyCode::Tab, KeyCode::Down]),
+                fixed_label(&[KeyCode::BackTab, KeyCode::Up]),
+                fixed_label(&[KeyCode::Enter]),
+                fixed_label(&[KeyCode::Esc])
+            )
+        } else {
+            format!(
+                "{}: Add | {}: Edit | {}: Delete | {}/{}: Move | {}: Save | {}: Discard",
+                keys(Action::EditorAdd),
+                keys(Action::EditorEdit),
+                keys(Action::EditorDelete),
+                keys(Action::EditorMoveUp),
+                keys(Action::EditorMoveDown),
+                keys(Action::EditorSave),
+                keys(Action::EditorDiscard)
+            )
+        }
+    });
+
+    let search_prompt = format!(
+        "/{}▏  {}/{}: Navigate | {}: Keep filter | {}: Clear",
+        app.search_query,
+        keys(Action::Up),
+        keys(Action::Down),
+        keys(Action::SearchDone),
+        keys(Action::ClearSearch)
+    );
+
+    let picker_hint = format!(
+        "{}: Toggle | {}: All/none | {}/{}: Target | {}: Install | {}: Cancel",
+        keys(Action::PickerToggle),
+        keys(Action::PickerToggleAll),
+        keys(Action::PrevChoice),
+        keys(Action::NextChoice),
+        keys(Action::PickerInstall),
+        keys(Action::PickerCancel)
+    );
+
+    let busy_text = format!(
+        "{} {}...",
+        SPINNER[app.spinner_frame % SPINNER.len()],
+        app.tasks.label
+    );
+
+    let default_hint = format!(
+        "{}: Switch pane | {}/{}: Navigate | {}: Search | {}: Mark | {}: Install | {}: Remove | {}: Dedupe | {}: Help | {}: Quit",
+        keys(Action::SwitchPane),
+        keys(Action::Up),
+        keys(Action::Down),
+        keys(Action::Search),
+        keys(Action::Mark),
+        keys(Action::Install),
+        keys(Action::Delete),
+        keys(Action::Dedupe),
+        keys(Action::Help),
+        keys(Action::Quit)
+    );
+
+    let status_text = if app.tasks.is_busy() {
+        busy_text.as_str()
+    } else if matches!(app.mode, AppMode::Search) {
+        search_prompt.as_str()
+    } else if let Some(hint) = &editor_hint {
+        hint.as_str()
+    } else if app.picker.is_some() {
+        picker_hint.as_str()
     } else if let Some(msg) = &app.status_message {
         msg.as_str()
     } else {
-        "TAB: Switch pane | ↑↓: Navigate | SPACE: Install | DEL: Remove | q: Quit"
+        default_hint.as_str()
     };
 
     let status = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::Yellow))
+        .style(Style::default().fg(app.theme.status))
         .block(Block::default().borders(Borders::ALL));
 
     f.render_widget(status, area);
 }
 
-fn render_confirmation_dialog(f: &mut Frame, app: &App) {
+fn render_confirmation_dialog(f: &mut Frame, app: &mut App) {
+    let answer = format!(
+        "{}: Confirm | {}: Cancel",
+        app.keymap.label(Action::Confirm),
+        app.keymap.label(Action::Cancel)
+    );
+    let choose = format!(
+        "{}/{}: Choose | {}: Apply",
+        app.keymap.label(Action::PrevChoice),
+        app.keymap.label(Action::NextChoice),
+        app.keymap.label(Action::ApplyChoice)
+    );
     let (title, message) = match &app.mode {
-        AppMode::ConfirmDelete(name) => (
-            "Confirm Deletion",
-            format!("Delete '{}'?\n\nPress 'y' to confirm, 'n' to cancel", name),
+        AppMode::ConfirmDelete(names) => {
+            let (subject, list) = group_list(names);
+            (
+                "Confirm Deletion",
+                format!("Delete {}?\n\n{}{}", subject, answer, list),
+            )
+        }
+        AppMode::ConfirmInstall(names) => {
+            let (subject, list) = group_list(names);
+            let conflicts: Vec<&str> = names
+                .iter()
+                .filter_map(|name| app.local_conflict(name))
+                .collect();
+            let choices = if conflicts.is_empty() {
+                String::new()
+            } else {
+                let installed = match conflicts.as_slice() {
+                    [name] => format!("'{}' is already installed", name),
+                    _ => format!("{} of them are already installed", conflicts.len()),
+                };
+                let choices: Vec<String> = ConflictPolicy::ALL
+                    .iter()
+                    .map(|p| {
+                        if *p == app.install_policy {
+                            format!("[{}]", p.label())
+                        } else {
+                            format!(" {} ", p.label())
+                        }
+                    })
+                    .collect();
+                format!(
+                    "\n\n{}, for those groups:\n{}\n{}",
+                    installed,
+                    choices.join(" "),
+                    choose
+                )
+            };
+            (
+                "Confirm Installation",
+                format!(
+                    "Install {}?{}\n\n{}{}{}",
+                    subject,
+                    choices,
+                    answer,
+                    list,
+                    warning_list(&app.install_warnings)
+                ),
+            )
+        }
+        AppMode::ConfirmDedupe(clusters) => {
+            let copies: usize = clusters.iter().map(|c| c.duplicates().len()).sum();
+            let mut message = match app.dedupe_mode {
+                DedupeMode::Remove => format!(
+                    "Remove {} duplicate teleports, keeping {} copies?\n\n",
+                    copies,
+                    clusters.len()
+                ),
+                DedupeMode::Merge => format!(
+                    "Merge {} duplicate teleports into {} kept copies?\n\n",
+                    copies,
+                    clusters.len()
+                ),
+            };
+            let choices: Vec<String> = DedupeMode::ALL
+                .iter()
+                .map(|m| {
+                    if *m == app.dedupe_mode {
+                        format!("[{}]", m.label())
+                    } else {
+                        format!(" {} ", m.label())
+                    }
+                })
+                .collect();
+            message.push_str(&format!(
+                "Merge folds tooltips into the kept copy, Remove drops them:\n{}\n\n{} | {}\n",
+                choices.join(" "),
+                choose,
+                answer
+            ));
+            let kept: Vec<String> = clusters
+                .iter()
+                .map(|cluster| {
+                    let keeper = cluster.keeper();
+                    format!(
+                        "{} / {} (+{})",
+                        keeper.group,
+                        keeper.name,
+                        cluster.duplicates().len()
+                    )
+                })
+                .collect();
+            message.push_str(&bullet_list(&kept));
+            ("Confirm Deduplication", message)
+        }
+        AppMode::ConfirmSplit(name) => (
+            "Confirm Split",
+            format!("Split '{}' into one group per map?\n\n{}", name, answer),
         ),
-        AppMode::ConfirmInstall(name) => (
-            "Confirm Installation",
-            format!("Install '{}'?\n\nPress 'y' to confirm, 'n' to cancel", name),
-        ),
-        AppMode::Normal => return,
+        AppMode::ConfirmMerge(names) => {
+            let target = app
+                .merge_targets(names)
+                .get(app.merge_target)
+                .map(|t| t.as_str())
+                .unwrap_or("");
+            let (subject, list) = group_list(names);
+            (
+                "Confirm Merge",
+                format!(
+                    "Merge {} into:\n\n← {} →\n\nDuplicate teleports are folded together.\n{} | {}{}",
+                    subject, target, choose, answer, list
+                ),
+            )
+        }
+        AppMode::Normal
+        | AppMode::EditGroup
+        | AppMode::ViewGroup(_)
+        | AppMode::PickTeleports
+        | AppMode::Search
+        | AppMode::ViewDiff => {
+            return;
+        }
     };
 
-    let area = centered_rect(60, 20, f.area());
+    let area = centered_rect(60, 40, f.area());
 
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(app.theme.popup_background));
+
+    let inner = block.inner(area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+    let buttons = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Length(3),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+        ])
+        .split(rows[1]);
+
+    let paragraph = Paragraph::new(message).style(Style::default().fg(app.theme.text));
+    let yes = Paragraph::new("[ Yes ]").style(
+        Style::default()
+            .fg(app.theme.added)
+            .add_modifier(Modifier::BOLD),
+    );
+    let no = Paragraph::new("[ No ]").style(
+        Style::default()
+            .fg(app.theme.removed)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    f.render_widget(paragraph, rows[0]);
+    f.render_widget(yes, buttons[1]);
+    f.render_widget(no, buttons[3]);
+    app.hit_areas.confirm_yes = Some(buttons[1]);
+    app.hit_areas.confirm_no = Some(buttons[3]);
+}
 
-    let paragraph = Paragraph::new(message)
-        .block(block)
-        .style(Style::default().fg(Color::White));
+fn render_help_popup(f: &mut Frame, app: &App) {
+    let mut lines = Vec::new();
+    for section in help_sections(app) {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            section.title,
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let width = section
+            .entries
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (key, text) in section.entries {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}  ", key, width = width),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(text),
+            ]));
+        }
+    }
+
+    let area = centered_rect(80, 80, f.area());
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Help ({}/{}: Scroll | {}: Close)",
+                    app.keymap.label(Action::Up),
+                    app.keymap.label(Action::Down),
+                    app.keymap.label(Action::Cancel)
+                ))
+                .borders(Borders::ALL)
+                .style(Style::default().bg(app.theme.popup_background)),
+        )
+        .style(Style::default().fg(app.theme.text))
+        .scroll((app.help_scroll, 0));
 
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
 }
 
+/// Subject and bullet list of the groups a confirmation applies to
+fn group_list(names: &[String]) -> (String, String) {
+    if let [name] = names {
+        return (format!("'{}'", name), String::new());
+    }
+    (
+        format!("these {} groups", names.len()),
+        format!("\n{}", bullet_list(names)),
+    )
+}
+
+/// The first few items as bullets, one per line, and how many were left out
+fn bullet_list(items: &[String]) -> String {
+    const MAX_LISTED: usize = 8;
+    let mut list = String::new();
+    for item in items.iter().take(MAX_LISTED) {
+        list.push_str(&format!("\n  • {}", item));
+    }
+    if items.len() > MAX_LISTED {
+        list.push_str(&format!("\n  …and {} more", items.len() - MAX_LISTED));
+    }
+    list
+}
+
+/// Suspicious teleports found before an install, empty if there are none
+fn warning_list(warnings: &[CoordinateWarning]) -> String {
+    const MAX_LISTED: usize = 5;
+    if warnings.is_empty() {
+        return String::new();
+    }
+    let mut list = format!("\n\n⚠ {} teleports look suspicious:", warnings.len());
+    for warning in warnings.iter().take(MAX_LISTED) {
+        list.push_str(&format!("\n  {}", warning));
+    }
+    if warnings.len() > MAX_LISTED {
+        list.push_str(&format!("\n  …and {} more", warnings.len() - MAX_LISTED));
+    }
+    list
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
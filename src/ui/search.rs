@@ -0,0 +1,97 @@
+/// A name matched by a fuzzy search query
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i32,
+    /// Char indices of the matched characters in the name
+    pub positions: Vec<usize>,
+}
+
+/// Match the query's characters in order anywhere in the name, ignoring case
+///
+/// Consecutive matches and matches at the start of a word score higher.
+/// Whitespace in the query is ignored, so "bj ma" matches "Bjora Marches".
+pub fn fuzzy_match(query: &str, name: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let chars: Vec<char> = name.chars().collect();
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut next = 0;
+    for wanted in &query {
+        let i = (next..chars.len()).find(|&i| chars[i].to_lowercase().eq([*wanted]))?;
+        score += 1;
+        if positions.last().is_some_and(|&last| last + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        score -= (i - next).min(3) as i32;
+        positions.push(i);
+        next = i + 1;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Indices of the names matching the query, best match first
+///
+/// An empty query matches every name in its original order.
+pub fn filter_names(query: &str, names: &[String]) -> Vec<usize> {
+    if query.trim().is_empty() {
+        return (0..names.len()).collect();
+    }
+
+    let mut matches: Vec<(usize, i32)> = names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| fuzzy_match(query, name).map(|m| (i, m.score)))
+        .collect();
+    matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn matches_in_order_ignoring_case_and_spaces() {
+        let found = fuzzy_match("bj ma", "Bjora Marches").unwrap();
+        assert_eq!(found.positions, [0, 1, 6, 7]);
+        assert_eq!(found.score, 27);
+
+        assert!(fuzzy_match("mb", "Bjora Marches").is_none());
+        assert!(fuzzy_match("bjz", "Bjora Marches").is_none());
+    }
+
+    #[test]
+    fn word_starts_and_runs_score_higher() {
+        let start = fuzzy_match("gate", "Gate of Maguuma").unwrap();
+        let middle = fuzzy_match("gate", "Stargates").unwrap();
+        let scattered = fuzzy_match("gate", "Great Collapse").unwrap();
+        assert!(start.score > middle.score);
+        assert!(middle.score > scattered.score);
+    }
+
+    #[test]
+    fn filter_ranks_best_first_and_keeps_ties_in_order() {
+        let list = names(&["Stargates", "Lions Arch", "Gate Hub", "Gate Camp"]);
+        assert_eq!(filter_names("gate", &list), [2, 3, 0]);
+    }
+
+    #[test]
+    fn empty_query_keeps_every_name() {
+        let list = names(&["Raids", "Strikes", "Fractals"]);
+        assert_eq!(filter_names("  ", &list), [0, 1, 2]);
+    }
+}
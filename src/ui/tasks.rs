@@ -0,0 +1,156 @@
+use crate::interop::{self, CsvImport};
+use crate::remote::RemoteProvider;
+use crate::types::TeleportGroup;
+use crate::{Result, TeleportError};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// What a fetched remote group was requested for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FetchPurpose {
+    /// Show the detail popup once loaded
+    Details,
+    /// Open the teleport picker once loaded
+    Picker,
+    /// Compare with the local version once loaded
+    Diff,
+}
+
+/// Outcome of remote work run in the background, sent back to the event loop
+pub enum TaskResult {
+    /// Names of the groups the remote offers
+    RemoteList(Result<Vec<String>>),
+    /// A single remote group fetched for inspection
+    Fetched {
+        name: String,
+        purpose: FetchPurpose,
+        result: Result<TeleportGroup>,
+    },
+    /// Remote groups fetched so they can be checked before confirming an install
+    InstallFetched {
+        names: Vec<String>,
+        fetched: Vec<(String, Result<TeleportGroup>)>,
+    },
+    /// Remote groups named like local groups, fetched to tell if they changed
+    CompareFetched(Vec<(String, Result<TeleportGroup>)>),
+    /// A spreadsheet picked for import and read, None if the dialog was cancelled
+    CsvRead(Option<Result<CsvImport>>),
+}
+
+/// Spawns remote work on the runtime and reports results over a channel
+pub struct TaskRunner {
+    remote: Arc<dyn RemoteProvider>,
+    sender: UnboundedSender<TaskResult>,
+    /// Number of tasks whose results have not been handled yet
+    pub pending: usize,
+    /// What the most recently started task is doing
+    pub label: String,
+}
+
+impl TaskRunner {
+    pub fn new(remote: Arc<dyn RemoteProvider>, sender: UnboundedSender<TaskResult>) -> Self {
+        Self {
+            remote,
+            sender,
+            pending: 0,
+            label: String::new(),
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Record that a task result has been handled
+    pub fn finish(&mut self) {
+        self.pending = self.pending.saturating_sub(1);
+    }
+
+    /// List the remote groups
+    pub fn list_remote(&mut self) {
+        let remote = self.remote.clone();
+        self.spawn("Loading remote groups", async move {
+            TaskResult::RemoteList(remote.list_available_groups().await)
+        });
+    }
+
+    /// Download a single remote group
+    pub fn fetch(&mut self, name: String, purpose: FetchPurpose) {
+        let remote = self.remote.clone();
+        let label = format!("Downloading '{}'", name);
+        self.spawn(label, async move {
+            let result = remote.fetch_group(&name).await;
+            TaskResult::Fetched {
+                name,
+                purpose,
+                result,
+            }
+        });
+    }
+
+    /// Download the groups of an install that are not cached yet
+    pub fn fetch_for_install(&mut self, names: Vec<String>, missing: Vec<String>) {
+        let remote = self.remote.clone();
+        let label = match missing.as_slice() {
+            [name] => format!("Downloading '{}'", name),
+            _ => format!("Downloading {} groups", missing.len()),
+        };
+        self.spawn(label, async move {
+            let mut fetched = Vec::with_capacity(missing.len());
+            for name in missing {
+                let result = remote.fetch_group(&name).await;
+                fetched.push((name, result));
+            }
+            TaskResult::InstallFetched { names, fetched }
+        });
+    }
+
+    /// Download remote groups in the background to compare them with their local copies
+    pub fn fetch_for_compare(&mut self, names: Vec<String>) {
+        let remote = self.remote.clone();
+        let label = format!("Checking {} groups for updates", names.len());
+        self.spawn(label, async move {
+            let mut fetched = Vec::with_capacity(names.len());
+            for name in names {
+                let result = remote.fetch_group(&name).await;
+                fetched.push((name, result));
+            }
+            TaskResult::CompareFetched(fetched)
+        });
+    }
+
+    /// Ask for a CSV or TSV file without blocking the event loop, then read it
+    pub fn import_csv(&mut self, dir: PathBuf) {
+        self.spawn("Choosing a file to import", async move {
+            let file = rfd::AsyncFileDialog::new()
+                .add_filter("Spreadsheet", &["csv", "tsv"])
+                .set_title("Import teleports")
+                .set_directory(&dir)
+                .pick_file()
+                .await;
+            let Some(file) = file else {
+                return TaskResult::CsvRead(None);
+            };
+            let path = file.path().to_path_buf();
+            let result = tokio::task::spawn_blocking(move || interop::read_csv(&path))
+                .await
+                .map_err(|e| TeleportError::IoError(std::io::Error::other(e)))
+                .and_then(|result| result);
+            TaskResult::CsvRead(Some(result))
+        });
+    }
+
+    fn spawn<F>(&mut self, label: impl Into<String>, task: F)
+    where
+        F: Future<Output = TaskResult> + Send + 'static,
+    {
+        self.pending += 1;
+        self.label = label.into();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            // The receiver only goes away when the app is shutting down
+            let _ = sender.send(task.await);
+        });
+    }
+}
@@ -0,0 +1,241 @@
+use crate::maps::MapDatabase;
+use crate::types::{Teleport, TeleportConfig, TeleportGroup};
+
+/// Map units (inches) per teleport coordinate unit (meters)
+const MAP_UNITS_PER_METER: f32 = 39.3701;
+
+/// Default slack around a map rect, as a fraction of its width/height
+const DEFAULT_TOLERANCE: f32 = 0.25;
+
+/// What is wrong with a teleport's coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordinateIssue {
+    /// At least one coordinate is NaN or infinite
+    NonFinite,
+    /// The map id is not in the map table, so bounds can't be checked
+    UnknownMap,
+    /// The position lies outside the map rect by more than the tolerance
+    OutOfBounds { distance: f32 },
+}
+
+impl CoordinateIssue {
+    /// Whether the coordinates themselves look wrong
+    ///
+    /// An unknown map only means the bounds couldn't be checked, the bundled map
+    /// table doesn't list every map.
+    pub fn is_suspicious(&self) -> bool {
+        !matches!(self, CoordinateIssue::UnknownMap)
+    }
+}
+
+impl std::fmt::Display for CoordinateIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoordinateIssue::NonFinite => write!(f, "coordinates are NaN or infinite"),
+            CoordinateIssue::UnknownMap => write!(f, "map id is unknown, bounds not checked"),
+            CoordinateIssue::OutOfBounds { distance } => {
+                write!(f, "{:.0} units outside the map bounds", distance)
+            }
+        }
+    }
+}
+
+/// A suspicious teleport found by the checker
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinateWarning {
+    pub group: String,
+    pub teleport: String,
+    /// Position of the teleport inside its group
+    pub index: usize,
+    pub map: u32,
+    pub issue: CoordinateIssue,
+}
+
+impl std::fmt::Display for CoordinateWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} / {} (map {}): {}",
+            self.group, self.teleport, self.map, self.issue
+        )
+    }
+}
+
+/// Validates teleport coordinates against per-map bounds
+///
+/// Coordinates are read as `[x, height, z]` in meters, and `x`/`z` are compared
+/// with the map rect after converting to map units.
+pub struct CoordinateChecker<'a> {
+    maps: &'a MapDatabase,
+    tolerance: f32,
+}
+
+impl<'a> CoordinateChecker<'a> {
+    /// Create a checker using the given map table
+    pub fn new(maps: &'a MapDatabase) -> Self {
+        Self {
+            maps,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Set how far outside a map rect a position may lie, as a fraction of its size
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Check a single teleport, returning the issue if it is suspicious
+    pub fn check_teleport(&self, teleport: &Teleport) -> Option<CoordinateIssue> {
+        if teleport.coordinates.iter().any(|c| !c.is_finite()) {
+            return Some(CoordinateIssue::NonFinite);
+        }
+
+        let Some(map) = self.maps.get(teleport.map) else {
+            return Some(CoordinateIssue::UnknownMap);
+        };
+
+        // Maps without known bounds only get the finite check
+        let [[x_min, y_min], [x_max, y_max]] = map.map_rect?;
+        let slack_x = (x_max - x_min) * self.tolerance;
+        let slack_y = (y_max - y_min) * self.tolerance;
+
+        let x = teleport.coordinates[0] * MAP_UNITS_PER_METER;
+        let y = teleport.coordinates[2] * MAP_UNITS_PER_METER;
+
+        let dx = (x_min - slack_x - x).max(x - x_max - slack_x).max(0.0);
+        let dy = (y_min - slack_y - y).max(y - y_max - slack_y).max(0.0);
+
+        if dx > 0.0 || dy > 0.0 {
+            Some(CoordinateIssue::OutOfBounds {
+                distance: dx.hypot(dy),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Check every teleport of a group
+    pub fn check_group(&self, group: &TeleportGroup) -> Vec<CoordinateWarning> {
+        group
+            .teleports
+            .iter()
+            .enumerate()
+            .filter_map(|(index, teleport)| {
+                self.check_teleport(teleport)
+                    .map(|issue| CoordinateWarning {
+                        group: group.name.clone(),
+                        teleport: teleport.name.clone(),
+                        index,
+                        map: teleport.map,
+                        issue,
+                    })
+            })
+            .collect()
+    }
+
+    /// Check every group of a teleport configuration
+    pub fn check_config(&self, config: &TeleportConfig) -> Vec<CoordinateWarning> {
+        config
+            .teleport_groups
+            .iter()
+            .flat_map(|group| self.check_group(group))
+            .collect()
+    }
+}
+
+impl Default for CoordinateChecker<'static> {
+    fn default() -> Self {
+        Self::new(MapDatabase::bundled())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maps::MapInfo;
+
+    fn maps() -> MapDatabase {
+        let map = |id, map_rect| MapInfo {
+            id,
+            name: format!("Map {}", id),
+            region: "Region".into(),
+            continent: "Tyria".into(),
+            map_rect,
+        };
+        MapDatabase::from_maps(vec![
+            map(1, Some([[-3937.01, -3937.01], [3937.01, 3937.01]])),
+            map(2, None),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn flags_non_finite_coordinates_first() {
+        let maps = maps();
+        let checker = CoordinateChecker::new(&maps);
+        for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let teleport = Teleport::new("Bad", 999, [0.0, bad, 0.0]);
+            assert_eq!(
+                checker.check_teleport(&teleport),
+                Some(CoordinateIssue::NonFinite)
+            );
+        }
+    }
+
+    #[test]
+    fn bounds_use_map_units_and_tolerance() {
+        let maps = maps();
+        // The rect spans 100 m each way, the default tolerance adds 50 m
+        let checker = CoordinateChecker::new(&maps);
+        assert_eq!(
+            checker.check_teleport(&Teleport::new("In", 1, [100.0, 5.0, -100.0])),
+            None
+        );
+        assert_eq!(
+            checker.check_teleport(&Teleport::new("Slack", 1, [149.0, 0.0, 0.0])),
+            None
+        );
+        let Some(CoordinateIssue::OutOfBounds { distance }) =
+            checker.check_teleport(&Teleport::new("Out", 1, [0.0, 0.0, 160.0]))
+        else {
+            panic!("expected an out of bounds issue");
+        };
+        assert!((distance - 10.0 * MAP_UNITS_PER_METER).abs() < 1.0);
+
+        let strict = CoordinateChecker::new(&maps).with_tolerance(0.0);
+        assert!(
+            strict
+                .check_teleport(&Teleport::new("Slack", 1, [149.0, 0.0, 0.0]))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn unknown_maps_and_maps_without_bounds() {
+        let maps = maps();
+        let checker = CoordinateChecker::new(&maps);
+        let unknown = checker.check_teleport(&Teleport::new("Unknown", 3, [0.0; 3]));
+        assert_eq!(unknown, Some(CoordinateIssue::UnknownMap));
+        assert!(!unknown.unwrap().is_suspicious());
+        assert_eq!(
+            checker.check_teleport(&Teleport::new("Unbounded", 2, [1e6, 0.0, 1e6])),
+            None
+        );
+    }
+
+    #[test]
+    fn group_warnings_keep_positions() {
+        let maps = maps();
+        let mut group = TeleportGroup::new("Group");
+        group.teleports.push(Teleport::new("Fine", 1, [0.0; 3]));
+        group
+            .teleports
+            .push(Teleport::new("Broken", 1, [f32::NAN, 0.0, 0.0]));
+        let warnings = CoordinateChecker::new(&maps).check_group(&group);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].index, 1);
+        assert_eq!(warnings[0].teleport, "Broken");
+        assert_eq!(warnings[0].group, "Group");
+    }
+}